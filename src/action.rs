@@ -0,0 +1,64 @@
+use crate::resource::bindings::Bindings;
+use crate::resource::highscores::NameEntry;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    PickColor(usize),
+    MoveCursor(Direction),
+    Place,
+    Clear,
+    Submit,
+    Undo,
+    Menu,
+    Hint,
+}
+
+impl Action {
+    pub fn name(&self) -> String {
+        match self {
+            Action::PickColor(index) => format!("color {}", index + 1),
+            Action::MoveCursor(Direction::Left) => "left".to_string(),
+            Action::MoveCursor(Direction::Right) => "right".to_string(),
+            Action::Place => "place".to_string(),
+            Action::Clear => "clear".to_string(),
+            Action::Submit => "submit".to_string(),
+            Action::Undo => "undo".to_string(),
+            Action::Menu => "menu".to_string(),
+            Action::Hint => "hint".to_string(),
+        }
+    }
+}
+
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_event::<Action>()
+            .init_resource::<Bindings>()
+            .add_system(bindings_system.system());
+    }
+}
+
+fn bindings_system(
+    keyboard: Res<Input<KeyCode>>,
+    bindings: Res<Bindings>,
+    name_entry: Option<Res<NameEntry>>,
+    mut actions: EventWriter<Action>,
+) {
+    if name_entry.is_some() {
+        return;
+    }
+    for binding in bindings.bindings.iter() {
+        if binding.keys.iter().any(|key| keyboard.just_pressed(*key)) {
+            actions.send(binding.action);
+        }
+    }
+}
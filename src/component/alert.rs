@@ -0,0 +1,5 @@
+use bevy::core::Timer;
+
+pub struct Alert {
+    pub timer: Timer,
+}
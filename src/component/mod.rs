@@ -1,6 +1,10 @@
+pub mod alert;
+pub mod camera;
 pub mod mouse;
 pub mod piece;
 pub mod position;
 pub mod select;
 pub mod selectable;
 pub mod selector;
+pub mod submit;
+pub mod touch;
@@ -1,4 +1,6 @@
+use crate::engine::Color;
+
 #[derive(Clone, Copy)]
 pub struct Piece {
-    pub color: mastermind::Color,
+    pub color: Color,
 }
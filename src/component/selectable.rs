@@ -12,10 +12,18 @@ pub struct Selectable {
 
 impl Selectable {
     pub fn new(position: Vec2, shape: SelectableShape) -> Self {
-        Selectable {
-            shape,
-            position,
-        }
+        Selectable { shape, position }
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn relayout(&mut self, position: Vec2, ratio: f32) {
+        self.position = position;
+        self.shape = match self.shape {
+            SelectableShape::Circle(rayon) => SelectableShape::Circle(rayon * ratio),
+        };
     }
 
     pub fn is_selected(&self, position: &Vec2) -> bool {
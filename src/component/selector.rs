@@ -4,4 +4,5 @@ use bevy::math::Vec2;
 pub struct Selector {
     pub position: Vec2,
     pub selected: bool,
+    pub pressed: bool,
 }
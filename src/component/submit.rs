@@ -0,0 +1,3 @@
+pub struct SubmitSlot {
+    pub row: u32,
+}
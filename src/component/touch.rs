@@ -0,0 +1,3 @@
+pub struct TouchState {
+    pub id: Option<u64>,
+}
@@ -0,0 +1,630 @@
+use crate::action::{Action, Direction};
+use crate::component::mouse::MouseState;
+use crate::component::piece::Piece;
+use crate::component::position::Position;
+use crate::component::select::Select;
+use crate::component::selectable::Selectable;
+use crate::component::selector::Selector;
+use crate::component::submit::SubmitSlot;
+use crate::component::touch::TouchState;
+use crate::engine;
+use crate::game::{
+    action_material, run_action, try_place_piece, ActionButton, EditEvent, FocusRing, HintEvent,
+};
+use crate::gamepad::PadEvent;
+use crate::mastermind_shape_bundler as MSB;
+use crate::resource::autoplay::AutoPlay;
+use crate::resource::button::ButtonMaterials;
+use crate::resource::color::MastermindColors;
+use crate::resource::focus::{Focus, FocusArea};
+use crate::resource::highscores::NameEntry;
+use crate::resource::mastermind::is_all_some;
+use crate::resource::options::PlayOptions;
+use crate::resource::rules::GameRules;
+use crate::resource::snapshots::Snapshots;
+use crate::resource::state::State as MState;
+use crate::resource::structure::Structure;
+use crate::state::AppState;
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::input::ElementState;
+use bevy::prelude::*;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(position_from_mouse.system())
+                .with_system(selector_from_mouse.system().label("Selector"))
+                .with_system(selector_from_touch.system().label("Selector"))
+                .with_system(drag_start.system().after("Selector"))
+                .with_system(drag_follow.system())
+                .with_system(drag_drop.system().label("Drop").after("Selector"))
+                .with_system(select.system().label("Piece").after("Drop"))
+                .with_system(action_system.system().label("Code").after("Piece"))
+                .with_system(gamepad_system.system().label("Code").after("Piece"))
+                .with_system(focus_ring_system.system().after("Code"))
+                .with_system(button_focus_system.system().after("Code"))
+                .with_system(play_code.system().label("Code").after("Piece"))
+                .with_system(clean_selector.system().after("Code"))
+                .with_system(submit_system.system().label("Code").after("Piece"))
+                .with_system(clear_from_mouse.system().label("Edit")),
+        );
+    }
+}
+
+fn position_from_mouse(mut mouse_pos: EventReader<CursorMoved>, query: Query<&mut Selector>) {
+    for event in mouse_pos.iter() {
+        query.for_each_mut(|mut selector| {
+            selector.position = event.position;
+        });
+    }
+}
+
+fn clean_selector(query: Query<&mut Selector>) {
+    query.for_each_mut(|mut selector| {
+        selector.selected = false;
+        selector.pressed = false;
+    });
+}
+
+fn selector_from_mouse(
+    mut mouse_button: EventReader<MouseButtonInput>,
+    query: Query<(&mut MouseState, &mut Selector)>,
+) {
+    for event in mouse_button.iter() {
+        query.for_each_mut(|(mut mouse_state, mut selector)| {
+            match (event.button, mouse_state.state, event.state) {
+                (MouseButton::Left, ElementState::Pressed, ElementState::Released) => {
+                    selector.selected = true;
+                    mouse_state.state = ElementState::Released;
+                }
+                (MouseButton::Left, ElementState::Released, ElementState::Pressed) => {
+                    selector.selected = false;
+                    selector.pressed = true;
+                    mouse_state.state = ElementState::Pressed;
+                }
+                _ => (),
+            }
+        });
+    }
+}
+
+fn selector_from_touch(
+    mut cmd: Commands,
+    mut touches: EventReader<TouchInput>,
+    windows: Res<Windows>,
+    query: Query<(&mut TouchState, &mut Selector)>,
+    drags: Query<Entity, With<Drag>>,
+) {
+    let height = windows.get_primary().map_or(0., |window| window.height());
+    for event in touches.iter() {
+        let position = touch_position(event.position, height);
+        query.for_each_mut(
+            |(mut touch_state, mut selector)| match (event.phase, touch_state.id) {
+                (TouchPhase::Started, None) => {
+                    selector.position = position;
+                    selector.selected = false;
+                    selector.pressed = true;
+                    touch_state.id = Some(event.id);
+                }
+                (TouchPhase::Moved, Some(id)) if id == event.id => {
+                    selector.position = position;
+                }
+                (TouchPhase::Ended, Some(id)) if id == event.id => {
+                    selector.position = position;
+                    selector.selected = true;
+                    touch_state.id = None;
+                }
+                (TouchPhase::Cancelled, Some(id)) if id == event.id => {
+                    drags.for_each(|entity| {
+                        cmd.entity(entity).despawn();
+                    });
+                    touch_state.id = None;
+                }
+                _ => (),
+            },
+        );
+    }
+}
+
+// Bevy only flips touch positions to a bottom-left origin on mobile targets,
+// cursor positions are flipped everywhere.
+fn touch_position(position: Vec2, height: f32) -> Vec2 {
+    if cfg!(any(target_os = "android", target_os = "ios")) {
+        position
+    } else {
+        Vec2::new(position.x, height - position.y)
+    }
+}
+
+struct Drag {
+    color: engine::Color,
+}
+
+fn drag_start(
+    mut cmd: Commands,
+    colors: Res<MastermindColors>,
+    structure: Res<Structure>,
+    selector: Query<&Selector>,
+    query: Query<(&Selectable, &Piece)>,
+    drags: Query<&Drag>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    if drags.iter().next().is_some() {
+        return;
+    }
+    for selector in selector.iter() {
+        if selector.pressed && !selector.selected {
+            for (selectable, piece) in query.iter() {
+                if selectable.is_selected(&selector.position) {
+                    let translation =
+                        structure.world_position(selector.position) + Vec3::new(0., 0., 2.);
+                    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+                    entities.push(
+                        cmd.spawn_bundle(MSB::build_piece(
+                            Transform {
+                                translation,
+                                ..Transform::default()
+                            },
+                            colors.pieces_colors[piece.color.value()],
+                            structure.piece_size,
+                        ))
+                        .insert(Drag { color: piece.color })
+                        .id(),
+                    );
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn action_system(
+    mut cmd: Commands,
+    mut actions: EventReader<Action>,
+    mut focus: ResMut<Focus>,
+    mut game: ResMut<MState>,
+    rules: Res<GameRules>,
+    options: Res<PlayOptions>,
+    colors: Res<MastermindColors>,
+    structure: Res<Structure>,
+    selector: Query<(Entity, Option<&Select>), With<Selector>>,
+    slots: Query<(Entity, &Position), With<Selectable>>,
+    mut hints: EventWriter<HintEvent>,
+    mut edits: EventWriter<EditEvent>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let actions: Vec<Action> = actions.iter().copied().collect();
+    if actions.is_empty() {
+        return;
+    }
+    let palette = rules.palette();
+    let code_length = game.code.len();
+    let submitting =
+        actions.contains(&Action::Submit) && options.confirm_submit && is_all_some(&game.code);
+    let mut picked = selector
+        .iter()
+        .find_map(|(_, select)| select.map(|select| select.piece.color));
+    for action in actions {
+        match action {
+            Action::PickColor(index) if index < palette.len() => {
+                picked = Some(palette[index]);
+                selector.for_each(|(entity, _)| {
+                    cmd.entity(entity).insert(Select {
+                        piece: Piece {
+                            color: palette[index],
+                        },
+                    });
+                });
+            }
+            Action::MoveCursor(Direction::Left) => {
+                focus.slot = (focus.slot + code_length - 1) % code_length;
+            }
+            Action::MoveCursor(Direction::Right) => {
+                focus.slot = (focus.slot + 1) % code_length;
+            }
+            Action::Clear => edits.send(EditEvent::ClearSlot(focus.slot)),
+            Action::Undo => edits.send(EditEvent::Undo),
+            Action::Hint => hints.send(HintEvent),
+            Action::Place if !submitting => {
+                if let Some(color) = picked {
+                    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+                    if place_focused(
+                        &mut cmd, &mut game, &rules, &slots, focus.slot, color, &colors,
+                        &structure, entities,
+                    ) {
+                        focus.slot = (focus.slot + 1) % code_length;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    focus.area = FocusArea::Slots;
+    focus.visible = true;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gamepad_system(
+    mut cmd: Commands,
+    mut pad_events: EventReader<PadEvent>,
+    mut focus: ResMut<Focus>,
+    mut game: ResMut<MState>,
+    mut app_state: ResMut<State<AppState>>,
+    mut autoplay: ResMut<AutoPlay>,
+    rules: Res<GameRules>,
+    colors: Res<MastermindColors>,
+    structure: Res<Structure>,
+    selector: Query<(Entity, Option<&Select>), With<Selector>>,
+    slots: Query<(Entity, &Position), With<Selectable>>,
+    buttons: Query<(Entity, &ActionButton, &GlobalTransform)>,
+    mut hints: EventWriter<HintEvent>,
+    mut edits: EventWriter<EditEvent>,
+    name_entry: Option<Res<NameEntry>>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let events: Vec<PadEvent> = pad_events.iter().copied().collect();
+    if name_entry.is_some() {
+        return;
+    }
+    let palette = rules.palette();
+    let code_length = game.code.len();
+    let order = button_order(
+        buttons
+            .iter()
+            .map(|(entity, _, transform)| (entity, transform)),
+    );
+    for event in events {
+        focus.visible = true;
+        let count = match focus.area {
+            FocusArea::Buttons => order.len().max(1),
+            FocusArea::Slots => code_length,
+            FocusArea::Palette => palette.len(),
+        };
+        let index = match focus.area {
+            FocusArea::Buttons => focus.button,
+            FocusArea::Slots => focus.slot,
+            FocusArea::Palette => focus.palette,
+        } % count;
+        let index = match event {
+            PadEvent::Up => {
+                focus.area = match focus.area {
+                    FocusArea::Palette => FocusArea::Slots,
+                    _ => FocusArea::Buttons,
+                };
+                continue;
+            }
+            PadEvent::Down => {
+                focus.area = match focus.area {
+                    FocusArea::Buttons => FocusArea::Slots,
+                    _ => FocusArea::Palette,
+                };
+                continue;
+            }
+            PadEvent::Left => (index + count - 1) % count,
+            PadEvent::Right => (index + 1) % count,
+            PadEvent::Start => continue,
+            PadEvent::Cancel => {
+                match focus.area {
+                    FocusArea::Slots => edits.send(EditEvent::ClearSlot(index)),
+                    _ => selector.for_each(|(entity, _)| {
+                        cmd.entity(entity).remove::<Select>();
+                    }),
+                }
+                index
+            }
+            PadEvent::Accept => match focus.area {
+                FocusArea::Buttons => {
+                    if let Some((_, button, _)) = order
+                        .get(index)
+                        .and_then(|entity| buttons.get(*entity).ok())
+                    {
+                        run_action(
+                            button.action,
+                            &mut app_state,
+                            &mut autoplay,
+                            &mut hints,
+                            &mut edits,
+                            &mut game,
+                        );
+                    }
+                    index
+                }
+                FocusArea::Slots => {
+                    let color = selector
+                        .iter()
+                        .find_map(|(_, select)| select.map(|select| select.piece.color));
+                    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+                    let placed = match color {
+                        Some(color) => place_focused(
+                            &mut cmd, &mut game, &rules, &slots, index, color, &colors, &structure,
+                            entities,
+                        ),
+                        None => false,
+                    };
+                    if placed {
+                        (index + 1) % count
+                    } else {
+                        index
+                    }
+                }
+                FocusArea::Palette => {
+                    selector.for_each(|(entity, _)| {
+                        cmd.entity(entity).insert(Select {
+                            piece: Piece {
+                                color: palette[index],
+                            },
+                        });
+                    });
+                    focus.area = FocusArea::Slots;
+                    continue;
+                }
+            },
+        };
+        match focus.area {
+            FocusArea::Buttons => focus.button = index,
+            FocusArea::Slots => focus.slot = index,
+            FocusArea::Palette => focus.palette = index,
+        }
+    }
+}
+
+fn button_order<'a>(buttons: impl Iterator<Item = (Entity, &'a GlobalTransform)>) -> Vec<Entity> {
+    let mut buttons: Vec<(Entity, Vec3)> = buttons
+        .map(|(entity, transform)| (entity, transform.translation))
+        .collect();
+    buttons.sort_by(|(_, a), (_, b)| {
+        b.y.round()
+            .partial_cmp(&a.y.round())
+            .unwrap()
+            .then(a.x.partial_cmp(&b.x).unwrap())
+    });
+    buttons.into_iter().map(|(entity, _)| entity).collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn place_focused(
+    cmd: &mut Commands,
+    game: &mut MState,
+    rules: &GameRules,
+    slots: &Query<(Entity, &Position), With<Selectable>>,
+    col: usize,
+    color: engine::Color,
+    colors: &MastermindColors,
+    structure: &Structure,
+    entities: &mut Vec<Entity>,
+) -> bool {
+    let row = game.row as u32;
+    match slots
+        .iter()
+        .find(|(_, position)| position.row == row && position.col == col as u32)
+    {
+        Some((slot, position)) => {
+            try_place_piece(
+                cmd, game, rules, slot, position, color, colors, structure, entities,
+            );
+            true
+        }
+        None => false,
+    }
+}
+
+fn focus_ring_system(
+    focus: Res<Focus>,
+    game: Res<MState>,
+    structure: Res<Structure>,
+    slots: Query<&Position, With<Selectable>>,
+    query: Query<(&mut Transform, &mut Visible), With<FocusRing>>,
+) {
+    let playable = slots.iter().next().is_some();
+    query.for_each_mut(|(mut transform, mut visible)| {
+        visible.is_visible = focus.visible && playable && focus.area != FocusArea::Buttons;
+        transform.translation = match focus.area {
+            FocusArea::Palette => structure.palette_position(focus.palette) + Vec3::new(0., 0., 1.),
+            _ => {
+                Vec3::new(
+                    focus.slot as f32 * structure.piece_size,
+                    game.row as f32 * structure.piece_size,
+                    1.,
+                ) + structure.boardgame_position
+            }
+        };
+    });
+}
+
+fn button_focus_system(
+    focus: Res<Focus>,
+    autoplay: Res<AutoPlay>,
+    button_materials: Res<ButtonMaterials>,
+    order: Query<(Entity, &GlobalTransform), With<ActionButton>>,
+    mut buttons: Query<(
+        &Interaction,
+        &mut Handle<ColorMaterial>,
+        &ActionButton,
+        &Children,
+    )>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !focus.is_changed() && !autoplay.is_changed() {
+        return;
+    }
+    let focused = button_order(order.iter())
+        .get(focus.button)
+        .copied()
+        .filter(|_| focus.visible && focus.area == FocusArea::Buttons);
+    for entity in button_order(order.iter()) {
+        if let Ok((interaction, mut material, button, children)) = buttons.get_mut(entity) {
+            if *interaction != Interaction::None {
+                continue;
+            }
+            let mut text = text_query.get_mut(children[0]).unwrap();
+            if focused == Some(entity) {
+                text.sections[0].value = button.hovered_label.to_string();
+                *material = button_materials.hovered.clone();
+            } else {
+                text.sections[0].value = button.label.to_string();
+                *material = action_material(button.action, &autoplay, &button_materials);
+            }
+        }
+    }
+}
+
+fn drag_follow(
+    structure: Res<Structure>,
+    selector: Query<&Selector>,
+    query: Query<&mut Transform, With<Drag>>,
+) {
+    for selector in selector.iter() {
+        query.for_each_mut(|mut transform| {
+            let translation = structure.world_position(selector.position);
+            transform.translation.x = translation.x;
+            transform.translation.y = translation.y;
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn drag_drop(
+    mut cmd: Commands,
+    mut game: ResMut<MState>,
+    colors: Res<MastermindColors>,
+    structure: Res<Structure>,
+    rules: Res<GameRules>,
+    selector: Query<&mut Selector>,
+    drags: Query<(Entity, &Drag)>,
+    slots: Query<(Entity, &Selectable, &Position)>,
+    pieces: Query<&Selectable, With<Piece>>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+    selector.for_each_mut(|mut selector| {
+        if !selector.selected {
+            return;
+        }
+        for (drag_entity, drag) in drags.iter() {
+            cmd.entity(drag_entity).despawn();
+            if pieces
+                .iter()
+                .any(|selectable| selectable.is_selected(&selector.position))
+            {
+                continue;
+            }
+            for (entity, selectable, position) in slots.iter() {
+                if position.row == game.row as u32 && selectable.is_selected(&selector.position) {
+                    try_place_piece(
+                        &mut cmd, &mut game, &rules, entity, position, drag.color, &colors,
+                        &structure, entities,
+                    );
+                }
+            }
+            selector.selected = false;
+        }
+    });
+}
+
+fn select(
+    mut cmd: Commands,
+    selector: Query<(Entity, &mut Selector)>,
+    query: Query<(&Selectable, &Piece)>,
+) {
+    selector.for_each_mut(|(entity, mut selector)| {
+        if selector.selected {
+            for (selectable, piece) in query.iter() {
+                if selectable.is_selected(&selector.position) {
+                    cmd.entity(entity).insert(Select {
+                        piece: piece.clone(),
+                    });
+                    selector.selected = false;
+                    break;
+                }
+            }
+        }
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_code(
+    mut cmd: Commands,
+    mut game: ResMut<MState>,
+    colors: Res<MastermindColors>,
+    structure: Res<Structure>,
+    rules: Res<GameRules>,
+    squery: Query<(&Select, &mut Selector)>,
+    query: Query<(Entity, &Selectable, &Position)>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+    squery.for_each_mut(|(select, mut selector)| {
+        if selector.selected {
+            query.for_each_mut(|(entity, selectable, position)| {
+                if selectable.is_selected(&selector.position) {
+                    if position.row == game.row as u32 {
+                        try_place_piece(
+                            &mut cmd,
+                            &mut game,
+                            &rules,
+                            entity,
+                            position,
+                            select.piece.color,
+                            &colors,
+                            &structure,
+                            entities,
+                        );
+                    }
+                    selector.selected = false;
+                }
+            });
+        }
+    });
+}
+
+fn clear_from_mouse(
+    mut mouse_button: EventReader<MouseButtonInput>,
+    mut events: EventWriter<EditEvent>,
+    game: Res<MState>,
+    selector: Query<&Selector>,
+    query: Query<(&Selectable, &Position)>,
+) {
+    for event in mouse_button.iter() {
+        if let (MouseButton::Right, ElementState::Released) = (event.button, event.state) {
+            for selector in selector.iter() {
+                for (selectable, position) in query.iter() {
+                    if position.row == game.row as u32 && selectable.is_selected(&selector.position)
+                    {
+                        events.send(EditEvent::ClearSlot(position.col as usize));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn submit_system(
+    mut state: ResMut<MState>,
+    options: Res<PlayOptions>,
+    mut actions: EventReader<Action>,
+    selector: Query<&mut Selector>,
+    query: Query<(&Selectable, &SubmitSlot)>,
+) {
+    let submit = actions.iter().any(|action| *action == Action::Submit);
+    if !options.confirm_submit || !is_all_some(&state.code) {
+        return;
+    }
+    if submit {
+        state.submitted = true;
+    }
+    selector.for_each_mut(|mut selector| {
+        if selector.selected {
+            for (selectable, slot) in query.iter() {
+                if slot.row == state.row as u32 && selectable.is_selected(&selector.position) {
+                    state.submitted = true;
+                    selector.selected = false;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,281 @@
+use crate::resource::rules::{GameRules, MAX_COLORS};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Color(usize);
+
+impl Color {
+    pub fn from_value(value: usize) -> Self {
+        Color(value)
+    }
+
+    pub fn blank() -> Self {
+        Color(MAX_COLORS)
+    }
+
+    pub fn value(&self) -> usize {
+        self.0
+    }
+}
+
+pub type Code = Vec<Color>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Try {
+    pub code: Code,
+    pub good: usize,
+    pub bad: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Playable {
+    pub tries: Vec<Try>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Finish {
+    pub code: Code,
+    pub tries: Vec<Try>,
+    pub win: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum State {
+    Playable(Playable),
+    Finish(Finish),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Game {
+    rules: GameRules,
+    secret: Code,
+    tries: Vec<Try>,
+    #[serde(default)]
+    timed_out: bool,
+}
+
+impl Game {
+    pub fn new(rules: &GameRules) -> Self {
+        let mut palette = rules.palette();
+        let secret = if rules.duplicates {
+            (0..rules.code_length)
+                .map(|_| palette[fastrand::usize(..palette.len())])
+                .collect()
+        } else {
+            fastrand::shuffle(&mut palette);
+            palette.into_iter().take(rules.code_length).collect()
+        };
+        Game {
+            rules: *rules,
+            secret,
+            tries: Vec::new(),
+            timed_out: false,
+        }
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    pub fn play(&mut self, code: Code) -> State {
+        if !self.rules.is_valid(&code) {
+            return self.state();
+        }
+        if let State::Playable(_) = self.state() {
+            let (good, bad) = evaluate(&self.secret, &code);
+            self.tries.push(Try { code, good, bad });
+        }
+        self.state()
+    }
+
+    pub fn time_out(&mut self) -> State {
+        if let State::Playable(_) = self.state() {
+            self.timed_out = true;
+        }
+        self.state()
+    }
+
+    pub fn state(&self) -> State {
+        let win = self.is_won();
+        if win || self.timed_out || self.tries.len() >= self.rules.max_attempts {
+            State::Finish(Finish {
+                code: self.secret.clone(),
+                tries: self.tries.clone(),
+                win,
+            })
+        } else {
+            State::Playable(Playable {
+                tries: self.tries.clone(),
+            })
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.rules.is_valid(&self.secret) {
+            return Err("secret does not match the rules".to_string());
+        }
+        if self.tries.len() > self.rules.max_attempts {
+            return Err("more tries than the rules allow".to_string());
+        }
+        for (i, tr) in self.tries.iter().enumerate() {
+            if !self.rules.is_valid(&tr.code)
+                || evaluate(&self.secret, &tr.code) != (tr.good, tr.bad)
+            {
+                return Err(format!("try {} is invalid", i + 1));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_won(&self) -> bool {
+        self.tries
+            .last()
+            .map_or(false, |tr| tr.good == self.rules.code_length)
+    }
+}
+
+pub fn evaluate(secret: &[Color], code: &[Color]) -> (usize, usize) {
+    let mut secret_left = [0usize; MAX_COLORS + 1];
+    let mut code_left = [0usize; MAX_COLORS + 1];
+    let mut good = 0;
+    for (s, c) in secret.iter().zip(code.iter()) {
+        if s == c {
+            good += 1;
+        } else {
+            secret_left[s.value()] += 1;
+            code_left[c.value()] += 1;
+        }
+    }
+    let bad = secret_left
+        .iter()
+        .zip(code_left.iter())
+        .map(|(s, c)| *s.min(c))
+        .sum();
+    (good, bad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(values: &[usize]) -> Code {
+        values
+            .iter()
+            .map(|value| Color::from_value(*value))
+            .collect()
+    }
+
+    fn game(rules: GameRules, secret: &[usize]) -> Game {
+        Game {
+            rules,
+            secret: code(secret),
+            tries: Vec::new(),
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn evaluate_all_correct() {
+        assert_eq!(evaluate(&code(&[0, 1, 2, 3]), &code(&[0, 1, 2, 3])), (4, 0));
+    }
+
+    #[test]
+    fn evaluate_nothing_in_common() {
+        assert_eq!(evaluate(&code(&[0, 1, 2, 3]), &code(&[4, 5, 4, 5])), (0, 0));
+    }
+
+    #[test]
+    fn evaluate_misplaced_only() {
+        assert_eq!(evaluate(&code(&[0, 1, 2, 3]), &code(&[3, 2, 1, 0])), (0, 4));
+    }
+
+    #[test]
+    fn evaluate_duplicates() {
+        assert_eq!(evaluate(&code(&[0, 0, 1, 1]), &code(&[1, 1, 0, 0])), (0, 4));
+        assert_eq!(evaluate(&code(&[0, 1, 2, 3]), &code(&[0, 0, 0, 0])), (1, 0));
+        assert_eq!(evaluate(&code(&[0, 0, 1, 2]), &code(&[0, 3, 0, 0])), (1, 1));
+        assert_eq!(evaluate(&code(&[0, 1, 1, 1]), &code(&[1, 0, 0, 0])), (0, 2));
+    }
+
+    #[test]
+    fn evaluate_blanks() {
+        let blank = Color::blank();
+        let secret = vec![blank, Color::from_value(0), Color::from_value(1), blank];
+        let guess = vec![blank, blank, Color::from_value(0), Color::from_value(2)];
+        assert_eq!(evaluate(&secret, &guess), (1, 2));
+    }
+
+    #[test]
+    fn state_playable_then_won() {
+        let mut game = game(GameRules::classic(), &[0, 1, 2, 3]);
+        match game.play(code(&[0, 1, 3, 2])) {
+            State::Playable(playable) => {
+                assert_eq!(playable.tries.len(), 1);
+                assert_eq!((playable.tries[0].good, playable.tries[0].bad), (2, 2));
+            }
+            State::Finish(_) => panic!("game should still be playable"),
+        }
+        match game.play(code(&[0, 1, 2, 3])) {
+            State::Finish(finish) => {
+                assert!(finish.win);
+                assert_eq!(finish.tries.len(), 2);
+                assert_eq!(finish.code, code(&[0, 1, 2, 3]));
+            }
+            State::Playable(_) => panic!("game should be won"),
+        }
+    }
+
+    #[test]
+    fn state_lost_after_max_attempts() {
+        let rules = GameRules::new(4, 6, 2);
+        let mut game = game(rules, &[0, 1, 2, 3]);
+        game.play(code(&[5, 5, 5, 5]));
+        match game.play(code(&[4, 4, 4, 4])) {
+            State::Finish(finish) => {
+                assert!(!finish.win);
+                assert_eq!(finish.tries.len(), 2);
+            }
+            State::Playable(_) => panic!("game should be lost"),
+        }
+        game.play(code(&[0, 1, 2, 3]));
+        assert_eq!(game.tries.len(), 2);
+    }
+
+    #[test]
+    fn state_ignores_invalid_codes() {
+        let rules = GameRules::classic();
+        let mut game = game(rules, &[0, 1, 2, 3]);
+        game.play(code(&[0, 1, 2]));
+        game.play(code(&[0, 1, 2, 7]));
+        assert!(game.tries.is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_corrupt_games() {
+        let rules = GameRules::classic();
+        let mut game = game(rules, &[0, 1, 2, 3]);
+        game.play(code(&[0, 0, 1, 1]));
+        assert!(game.validate().is_ok());
+        game.tries[0].good = 3;
+        assert!(game.validate().is_err());
+        game.tries[0].code = code(&[0, 0, 1, 42]);
+        assert!(game.validate().is_err());
+        game.tries.clear();
+        game.secret = code(&[0, 1, 2]);
+        assert!(game.validate().is_err());
+        game.secret = code(&[0, 1, 2, 99]);
+        assert!(game.validate().is_err());
+    }
+
+    #[test]
+    fn state_lost_on_time_out() {
+        let mut game = game(GameRules::classic(), &[0, 1, 2, 3]);
+        game.play(code(&[5, 5, 5, 5]));
+        match game.time_out() {
+            State::Finish(finish) => assert!(!finish.win),
+            State::Playable(_) => panic!("game should be lost"),
+        }
+        game.play(code(&[0, 1, 2, 3]));
+        assert_eq!(game.tries.len(), 1);
+    }
+}
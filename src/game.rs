@@ -1,55 +1,134 @@
-use crate::component::mouse::MouseState;
+use crate::action::Action;
+use crate::component::alert::Alert;
 use crate::component::piece::Piece;
 use crate::component::position::Position;
-use crate::component::select::Select;
 use crate::component::selectable::{Selectable, SelectableShape};
-use crate::component::selector::Selector;
+use crate::component::submit::SubmitSlot;
+use crate::engine;
+use crate::gamepad::PadEvent;
 use crate::mastermind_shape_bundler as MSB;
+use crate::resource::assets::Assets as MAssets;
+use crate::resource::autoplay::AutoPlay;
+use crate::resource::bindings::Bindings;
 use crate::resource::button::ButtonMaterials;
+use crate::resource::clock::{format_time, Clock};
 use crate::resource::color::MastermindColors;
-use crate::resource::mastermind::{is_all_some, some_code_to_code};
+use crate::resource::focus::Focus;
+use crate::resource::highscores::{HighScores, NameEntry, NAME_LENGTH};
+use crate::resource::mastermind::{find_duplicate, is_all_some, some_code_to_code};
+use crate::resource::options::PlayOptions;
+use crate::resource::rules::{Difficulty, GameRules};
+use crate::resource::savegame::{Resume, SavedGame};
+use crate::resource::score::{Score, SessionScores};
 use crate::resource::snapshots::Snapshots;
 use crate::resource::state::State as MState;
-use crate::resource::assets::Assets as MAssets;
-use crate::resource::structure::Structure;
+use crate::resource::stats::Stats;
+use crate::resource::structure::{Structure, HISTORY_SCALE};
+use crate::solver::{all_codes, candidates, contradiction, next_guess};
 use crate::{resource::mastermind::Mastermind, state::AppState};
-use bevy::input::mouse::MouseButtonInput;
-use bevy::input::ElementState;
 use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use bevy::window::{ReceivedCharacter, WindowCloseRequested};
 use bevy_prototype_lyon::entity::ShapeBundle;
+use futures_lite::future;
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.add_system_set(
-            SystemSet::on_enter(AppState::InGame)
-                .with_system(setup.system().label("Setup"))
-                .with_system(draw_background.system().after("Setup"))
-                .with_system(draw_pieces.system().after("Setup"))
-                .with_system(draw_ui.system().after("Setup")),
-        )
-        .add_system_set(
-            SystemSet::on_update(AppState::InGame)
-                .with_system(position_from_mouse.system())
-                .with_system(selector_from_mouse.system().label("Selector"))
-                .with_system(select.system().label("Piece").after("Selector"))
-                .with_system(play_code.system().label("Code").after("Piece"))
-                .with_system(button_system.system())
-                .with_system(clean_selector.system().after("Code"))
-                .with_system(game_update.system()),
-        )
-        .add_system_set(SystemSet::on_exit(AppState::InGame).with_system(clear.system()));
+        app.init_resource::<Resume>()
+            .init_resource::<SessionScores>()
+            .add_system(save_on_close.system())
+            .add_event::<HintEvent>()
+            .add_event::<EditEvent>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(setup.system().label("Setup"))
+                    .with_system(draw_background.system().after("Setup"))
+                    .with_system(draw_pieces.system().after("Setup"))
+                    .with_system(draw_ui.system().after("Setup")),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(hud_system.system())
+                    .with_system(button_system.system())
+                    .with_system(alert_system.system())
+                    .with_system(auto_play.system().label("Code"))
+                    .with_system(hint_system.system().label("Code"))
+                    .with_system(edit_system.system().label("Code").after("Edit"))
+                    .with_system(submit_marker_system.system().after("Code"))
+                    .with_system(warning_system.system().label("Warning").after("Code"))
+                    .with_system(game_update.system().after("Warning"))
+                    .with_system(restore_system.system())
+                    .with_system(reorient_system.system().after("Relayout"))
+                    .with_system(autosave_system.system().after("Warning"))
+                    .with_system(clock_system.system().label("Clock").after("Code"))
+                    .with_system(blitz_system.system().after("Clock").before("Warning"))
+                    .with_system(game_over_system.system().label("GameOver").after("Warning"))
+                    .with_system(name_entry_system.system().after("GameOver")),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::InGame)
+                    .with_system(save_game.system())
+                    .with_system(save_name_entry.system())
+                    .with_system(clear.system()),
+            );
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn setup(
     mut commands: Commands,
     mut app_state: ResMut<State<AppState>>,
     mut snapshots: ResMut<Snapshots>,
+    mut structure: ResMut<Structure>,
+    mut rules: ResMut<GameRules>,
+    mut difficulty: ResMut<Difficulty>,
+    mut resume: ResMut<Resume>,
+    options: Res<PlayOptions>,
+    window: Res<WindowDescriptor>,
+    time: Res<Time>,
 ) {
-    commands.insert_resource(Mastermind::new());
-    commands.insert_resource(MState::new());
+    let saved = if resume.requested {
+        resume.requested = false;
+        SavedGame::load()
+    } else {
+        None
+    };
+    match saved {
+        Some(saved) => {
+            *rules = *saved.game.rules();
+            if let Some(preset) = Difficulty::ALL
+                .iter()
+                .find(|preset| preset.rules() == *rules)
+            {
+                *difficulty = *preset;
+            }
+            let mut state = MState::new(&rules);
+            state.row = saved.row;
+            state.code = saved.code;
+            state.hints = saved.hints;
+            state.autoplayed = saved.autoplayed;
+            commands.insert_resource(Clock::resumed(
+                saved.row,
+                saved.elapsed,
+                saved.row_elapsed,
+                time.seconds_since_startup(),
+            ));
+            commands.insert_resource(Mastermind { state: saved.game });
+            commands.insert_resource(state);
+            commands.insert_resource(Restore);
+        }
+        None => {
+            commands.insert_resource(Mastermind::new(&rules));
+            commands.insert_resource(MState::new(&rules));
+            commands.insert_resource(Clock::new());
+        }
+    }
+    let size = (window.width, window.height);
+    *structure = Structure::new(size, &rules, options.layout.is_landscape(size));
+    commands.insert_resource(AutoPlay::new());
+    commands.insert_resource(Focus::new());
     snapshots.snap(&String::from("Game"), Vec::new());
     app_state.push(AppState::Loading).unwrap();
 }
@@ -59,57 +138,136 @@ pub fn draw_ui(
     mut snapshots: ResMut<Snapshots>,
     button_materials: Res<ButtonMaterials>,
     assets: Res<MAssets>,
+    structure: Res<Structure>,
 ) {
     let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
     entities.push(cmds.spawn_bundle(UiCameraBundle::default()).id());
+    let buttons = vec![
+        (
+            ActionButton::new(GameAction::AutoPlay, "auto", "AUTO"),
+            button_materials.normal.clone(),
+        ),
+        (
+            ActionButton::new(GameAction::Undo, "undo", "UNDO"),
+            button_materials.normal.clone(),
+        ),
+        (
+            ActionButton::new(GameAction::ClearRow, "clear", "CLEAR"),
+            button_materials.normal.clone(),
+        ),
+        (
+            ActionButton::new(GameAction::Hint, "hint", "HINT"),
+            button_materials.normal.clone(),
+        ),
+        (
+            ActionButton::new(GameAction::Pause, "  ...  ", "PAUSE"),
+            button_materials.alerte.clone(),
+        ),
+    ];
     entities.push(
-        cmds.spawn()
-            .insert_bundle(ButtonBundle {
-                style: Style {
-                    size: Size::new(Val::Px(75.0), Val::Px(50.0)),
-                    position_type: PositionType::Absolute,
-                    position: Rect {
-                        right: Val::Px(0.0),
-                        top: Val::Px(0.0),
-                        ..Default::default()
-                    },
+        cmds.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(structure.hud_height)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
                     ..Default::default()
                 },
-                material: button_materials.alerte.clone(),
+                justify_content: JustifyContent::SpaceBetween,
                 ..Default::default()
-            }).with_children(|parent| {
-                parent.spawn().insert_bundle(TextBundle {
-                    text: Text::with_section(
-                        "  ...  ",
-                        TextStyle {
-                            font: assets.font.clone(),
-                            font_size: 40.0,
-                            color: Color::rgb(0.9, 0.9, 0.9),
-                        },
-                        TextAlignment{
-                            horizontal: HorizontalAlign::Center,
-                            vertical: VerticalAlign::Center,
-                        },
-                    ),
-                    ..Default::default()
-                });
-            })
-            .insert(ActionButton::new(|state| state.pop().unwrap()))
-            .id(),
+            },
+            material: button_materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (action, material) in buttons {
+                spawn_action_button(parent, action, material, Val::Percent(19.0), &assets);
+            }
+        })
+        .insert(Hud)
+        .id(),
     );
 }
 
+struct Hud;
+
+fn hud_system(structure: Res<Structure>, query: Query<&mut Style, With<Hud>>) {
+    if structure.is_changed() {
+        query.for_each_mut(|mut style| {
+            style.size.height = Val::Px(structure.hud_height);
+        });
+    }
+}
+
+fn spawn_action_button(
+    parent: &mut ChildBuilder,
+    action: ActionButton,
+    material: Handle<ColorMaterial>,
+    width: Val,
+    assets: &MAssets,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(width, Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn().insert_bundle(TextBundle {
+                text: Text::with_section(
+                    action.label,
+                    TextStyle {
+                        font: assets.font.clone(),
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        vertical: VerticalAlign::Center,
+                    },
+                ),
+                ..Default::default()
+            });
+        })
+        .insert(action);
+}
+
 pub fn draw_background(
     mut commands: Commands,
     colors: Res<MastermindColors>,
     structure: Res<Structure>,
+    rules: Res<GameRules>,
+    assets: Res<MAssets>,
     mut snapshots: ResMut<Snapshots>,
 ) {
     let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+    spawn_background(
+        &mut commands,
+        entities,
+        &colors,
+        &structure,
+        &rules,
+        &assets,
+    );
+}
 
+fn spawn_background(
+    commands: &mut Commands,
+    entities: &mut Vec<Entity>,
+    colors: &MastermindColors,
+    structure: &Structure,
+    rules: &GameRules,
+    assets: &MAssets,
+) {
     let initial_position = structure.boardgame_position;
-    for row in 0..10 {
-        for col in 0..4 {
+    for row in 0..rules.max_attempts as u32 {
+        for col in 0..rules.code_length as u32 {
             let local_translation = Vec3::new(
                 (col as f32) * structure.piece_size,
                 (row as f32) * structure.piece_size,
@@ -130,7 +288,7 @@ pub fn draw_background(
             entities.push(
                 commands
                     .spawn()
-                    .insert(Position { row: row, col: col })
+                    .insert(Position { row, col })
                     .insert(Selectable::new(
                         Vec2::new(
                             local_translation.x + (structure.piece_size / 2.),
@@ -141,15 +299,29 @@ pub fn draw_background(
                     .id(),
             );
         }
+        let result_translation = Vec3::new(
+            structure.piece_size * rules.code_length as f32,
+            (row as f32) * structure.piece_size,
+            0.,
+        ) + initial_position;
+        entities.push(
+            commands
+                .spawn()
+                .insert(SubmitSlot { row })
+                .insert(Selectable::new(
+                    Vec2::new(
+                        result_translation.x + (structure.piece_size / 2.),
+                        result_translation.y + (structure.piece_size / 2.),
+                    ),
+                    SelectableShape::Circle(structure.piece_size / 2.),
+                ))
+                .id(),
+        );
         entities.push(
             commands
                 .spawn_bundle(MSB::build_result_case(
                     Transform {
-                        translation: Vec3::new(
-                            structure.piece_size * 4.,
-                            (row as f32) * structure.piece_size,
-                            0.,
-                        ) + initial_position,
+                        translation: result_translation,
                         ..Transform::default()
                     },
                     colors.result_case_colors,
@@ -158,16 +330,13 @@ pub fn draw_background(
                 .id(),
         );
     }
-    for col in 0..4 {
+    for col in 0..rules.code_length {
         entities.push(
             commands
                 .spawn_bundle(MSB::build_secret_case(
                     Transform {
-                        translation: Vec3::new(
-                            (col as f32) * structure.piece_size,
-                            structure.piece_size * 10.,
-                            0.,
-                        ) + initial_position,
+                        translation: Vec3::new((col as f32) * structure.piece_size, 0., 0.)
+                            + structure.secrets_position,
                         ..Transform::default()
                     },
                     colors.secret_case_hidden_colors,
@@ -176,19 +345,163 @@ pub fn draw_background(
                 .id(),
         );
     }
+    entities.push(
+        commands
+            .spawn_bundle(remaining_text(
+                all_codes(rules).len(),
+                Vec3::new(
+                    structure.piece_size * (rules.code_length + 1) as f32,
+                    0.,
+                    0.,
+                ) + structure.secrets_position,
+                structure,
+                colors,
+                assets,
+            ))
+            .insert(Remaining)
+            .id(),
+    );
+    entities.push(
+        commands
+            .spawn_bundle(small_text(
+                String::new(),
+                Vec3::new(
+                    structure.piece_size * (rules.code_length + 1) as f32,
+                    structure.piece_size * 0.32,
+                    0.,
+                ) + structure.secrets_position,
+                structure,
+                colors,
+                assets,
+            ))
+            .insert(ClockText)
+            .id(),
+    );
+    let mut focus_ring = MSB::build_outlined_rect(
+        Transform {
+            translation: initial_position + Vec3::new(0., 0., 1.),
+            ..Transform::default()
+        },
+        colors.focus_colors,
+        structure.piece_size * 0.9,
+        structure.piece_size * 0.9,
+        structure.piece_size * 0.08,
+    );
+    focus_ring.visible.is_visible = false;
+    entities.push(commands.spawn_bundle(focus_ring).insert(FocusRing).id());
+}
+
+struct Remaining;
+
+struct ClockText;
+
+pub struct FocusRing;
+
+fn remaining_text(
+    count: usize,
+    translation: Vec3,
+    structure: &Structure,
+    colors: &MastermindColors,
+    assets: &MAssets,
+) -> Text2dBundle {
+    Text2dBundle {
+        text: Text::with_section(
+            count.to_string(),
+            TextStyle {
+                font: assets.font.clone(),
+                font_size: structure.piece_size * 0.35,
+                color: colors.remaining_color,
+            },
+            TextAlignment {
+                horizontal: HorizontalAlign::Center,
+                vertical: VerticalAlign::Center,
+            },
+        ),
+        transform: Transform {
+            translation,
+            ..Transform::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn small_text(
+    value: String,
+    translation: Vec3,
+    structure: &Structure,
+    colors: &MastermindColors,
+    assets: &MAssets,
+) -> Text2dBundle {
+    Text2dBundle {
+        text: Text::with_section(
+            value,
+            TextStyle {
+                font: assets.font.clone(),
+                font_size: structure.piece_size * 0.25,
+                color: colors.remaining_color,
+            },
+            TextAlignment {
+                horizontal: HorizontalAlign::Center,
+                vertical: VerticalAlign::Center,
+            },
+        ),
+        transform: Transform {
+            translation,
+            ..Transform::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn history_text(
+    tr: &engine::Try,
+    translation: Vec3,
+    structure: &Structure,
+    colors: &MastermindColors,
+    assets: &MAssets,
+) -> Text2dBundle {
+    Text2dBundle {
+        text: Text::with_section(
+            format!("{} / {}", tr.good, tr.bad),
+            TextStyle {
+                font: assets.font.clone(),
+                font_size: structure.piece_size * 0.35,
+                color: colors.remaining_color,
+            },
+            TextAlignment {
+                horizontal: HorizontalAlign::Left,
+                vertical: VerticalAlign::Center,
+            },
+        ),
+        transform: Transform {
+            translation,
+            ..Transform::default()
+        },
+        ..Default::default()
+    }
 }
 
 pub fn draw_pieces(
     mut commands: Commands,
     colors: Res<MastermindColors>,
     structure: Res<Structure>,
+    rules: Res<GameRules>,
     mut snapshots: ResMut<Snapshots>,
 ) {
     let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
-    let initial_position = structure.pieces_position;
-    for col in 0..6 {
+    spawn_pieces(&mut commands, entities, &colors, &structure, &rules);
+}
+
+fn spawn_pieces(
+    commands: &mut Commands,
+    entities: &mut Vec<Entity>,
+    colors: &MastermindColors,
+    structure: &Structure,
+    rules: &GameRules,
+) {
+    for (col, color) in rules.palette().into_iter().enumerate() {
         let transform = Transform {
-            translation: Vec3::new((col as f32) * structure.piece_size, 0., 0.) + initial_position,
+            translation: structure.palette_position(col),
             ..Transform::default()
         };
         entities.push(
@@ -204,7 +517,7 @@ pub fn draw_pieces(
             commands
                 .spawn_bundle(MSB::build_piece(
                     transform,
-                    colors.pieces_colors[col as usize],
+                    colors.pieces_colors[color.value()],
                     structure.piece_size,
                 ))
                 .insert(Selectable::new(
@@ -214,9 +527,7 @@ pub fn draw_pieces(
                     ),
                     SelectableShape::Circle(structure.piece_size / 2.),
                 ))
-                .insert(Piece {
-                    color: mastermind::Color::from_value(col as usize),
-                })
+                .insert(Piece { color })
                 .id(),
         );
     }
@@ -231,213 +542,1115 @@ fn clear(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
     }
 }
 
-fn position_from_mouse(mut mouse_pos: EventReader<CursorMoved>, query: Query<&mut Selector>) {
-    for event in mouse_pos.iter() {
-        query.for_each_mut(|mut selector| {
-            selector.position = event.position;
-        });
+#[allow(clippy::too_many_arguments)]
+pub fn try_place_piece(
+    cmd: &mut Commands,
+    game: &mut MState,
+    rules: &GameRules,
+    entity: Entity,
+    position: &Position,
+    color: engine::Color,
+    colors: &MastermindColors,
+    structure: &Structure,
+    entities: &mut Vec<Entity>,
+) {
+    let duplicate =
+        find_duplicate(&game.code, position.col as usize, color).filter(|_| !rules.duplicates);
+    if let Some(col) = duplicate {
+        let local_translation = Vec3::new(
+            (col as f32) * structure.piece_size,
+            (position.row as f32) * structure.piece_size,
+            1.,
+        ) + structure.boardgame_position;
+        entities.push(
+            cmd.spawn_bundle(MSB::build_outlined_rect(
+                Transform {
+                    translation: local_translation,
+                    ..Transform::default()
+                },
+                colors.alert_colors,
+                structure.piece_size,
+                structure.piece_size,
+                structure.piece_size * 0.08,
+            ))
+            .insert(Alert {
+                timer: Timer::from_seconds(0.6, false),
+            })
+            .id(),
+        );
+    } else {
+        place_piece(cmd, game, entity, position, color, colors, structure);
     }
 }
 
-fn clean_selector(query: Query<&mut Selector>) {
-    query.for_each_mut(|mut selector| selector.selected = false);
+fn place_piece(
+    cmd: &mut Commands,
+    game: &mut MState,
+    entity: Entity,
+    position: &Position,
+    color: engine::Color,
+    colors: &MastermindColors,
+    structure: &Structure,
+) {
+    let col = position.col as usize;
+    let previous = game.code[col];
+    game.history.push((col, previous));
+    set_slot(cmd, game, entity, position, Some(color), colors, structure);
 }
 
-fn selector_from_mouse(
-    mut mouse_button: EventReader<MouseButtonInput>,
-    query: Query<(&mut MouseState, &mut Selector)>,
+fn set_slot(
+    cmd: &mut Commands,
+    game: &mut MState,
+    entity: Entity,
+    position: &Position,
+    color: Option<engine::Color>,
+    colors: &MastermindColors,
+    structure: &Structure,
 ) {
-    for event in mouse_button.iter() {
-        query.for_each_mut(|(mut mouse_state, mut selector)| {
-            match (event.button, mouse_state.state, event.state) {
-                (MouseButton::Left, ElementState::Pressed, ElementState::Released) => {
-                    selector.selected = true;
-                    mouse_state.state = ElementState::Released;
+    if game.code[position.col as usize].is_some() {
+        cmd.entity(entity).remove_bundle::<ShapeBundle>();
+    }
+    game.code[position.col as usize] = color;
+    game.confirmed = false;
+    game.submitted = false;
+    if let Some(color) = color {
+        cmd.entity(entity)
+            .insert_bundle(slot_piece(position, color, colors, structure));
+    }
+}
+
+fn slot_piece(
+    position: &Position,
+    color: engine::Color,
+    colors: &MastermindColors,
+    structure: &Structure,
+) -> ShapeBundle {
+    let local_translation = Vec3::new(
+        (position.col as f32) * structure.piece_size,
+        (position.row as f32) * structure.piece_size,
+        0.,
+    ) + structure.boardgame_position;
+    MSB::build_piece(
+        Transform {
+            translation: local_translation,
+            ..Transform::default()
+        },
+        colors.pieces_colors[color.value()],
+        structure.piece_size,
+    )
+}
+
+pub enum EditEvent {
+    Undo,
+    ClearRow,
+    ClearSlot(usize),
+}
+
+fn edit_system(
+    mut cmd: Commands,
+    mut events: EventReader<EditEvent>,
+    mut game: ResMut<MState>,
+    colors: Res<MastermindColors>,
+    structure: Res<Structure>,
+    query: Query<(Entity, &Position), With<Selectable>>,
+) {
+    let row = game.row as u32;
+    let slots: Vec<(Entity, &Position)> = query
+        .iter()
+        .filter(|(_, position)| position.row == row)
+        .collect();
+    for event in events.iter() {
+        let changes: Vec<(usize, Option<engine::Color>)> = match event {
+            EditEvent::Undo => game.history.pop().into_iter().collect(),
+            EditEvent::ClearRow => (0..game.code.len())
+                .filter(|col| game.code[*col].is_some())
+                .map(|col| (col, None))
+                .collect(),
+            EditEvent::ClearSlot(col) => {
+                if game.code[*col].is_some() {
+                    vec![(*col, None)]
+                } else {
+                    Vec::new()
                 }
-                (MouseButton::Left, ElementState::Released, ElementState::Pressed) => {
-                    selector.selected = false;
-                    mouse_state.state = ElementState::Pressed;
+            }
+        };
+        let record = !matches!(event, EditEvent::Undo);
+        for (col, color) in changes {
+            if let Some((entity, position)) = slots.iter().find(|(_, p)| p.col == col as u32) {
+                if record {
+                    let previous = game.code[col];
+                    game.history.push((col, previous));
                 }
-                _ => (),
+                set_slot(
+                    &mut cmd, &mut game, *entity, position, color, &colors, &structure,
+                );
             }
-        });
+        }
     }
 }
 
-fn select(
+#[allow(clippy::too_many_arguments)]
+fn auto_play(
     mut cmd: Commands,
-    selector: Query<(Entity, &mut Selector)>,
-    query: Query<(&Selectable, &Piece)>,
+    time: Res<Time>,
+    mut autoplay: ResMut<AutoPlay>,
+    mastermind: Res<Mastermind>,
+    mut game: ResMut<MState>,
+    colors: Res<MastermindColors>,
+    structure: Res<Structure>,
+    query: Query<(Entity, &Position), With<Selectable>>,
+    pool: Res<AsyncComputeTaskPool>,
 ) {
-    selector.for_each_mut(|(entity, mut selector)| {
-        if selector.selected {
-            for (selectable, piece) in query.iter() {
-                if selectable.is_selected(&selector.position) {
-                    cmd.entity(entity).insert(Select {
-                        piece: piece.clone(),
-                    });
-                    selector.selected = false;
-                    break;
+    if !autoplay.enabled || !autoplay.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let tries = match mastermind.state.state() {
+        engine::State::Playable(playable) => playable.tries,
+        engine::State::Finish(_) => {
+            autoplay.enabled = false;
+            return;
+        }
+    };
+    let row = game.row;
+    if autoplay
+        .guess
+        .as_ref()
+        .map_or(true, |(guess_row, _)| *guess_row != row)
+    {
+        match autoplay.pending.take() {
+            Some((guess_row, mut task)) if guess_row == row => {
+                match future::block_on(future::poll_once(&mut task)) {
+                    Some(guess) => autoplay.guess = guess.map(|guess| (row, guess)),
+                    None => {
+                        autoplay.pending = Some((row, task));
+                        return;
+                    }
                 }
             }
+            _ => {
+                let rules = *mastermind.state.rules();
+                let task = pool.spawn(async move { next_guess(&rules, &tries) });
+                autoplay.pending = Some((row, task));
+                return;
+            }
         }
-    });
+    }
+    if let Some((_, guess)) = autoplay.guess.clone() {
+        let mut slots: Vec<(Entity, &Position)> = query
+            .iter()
+            .filter(|(_, position)| position.row == row as u32)
+            .collect();
+        slots.sort_by_key(|(_, position)| position.col);
+        for (entity, position) in slots.iter() {
+            let col = position.col as usize;
+            if game.code[col].map_or(false, |color| color != guess[col]) {
+                let previous = game.code[col];
+                game.history.push((col, previous));
+                set_slot(
+                    &mut cmd, &mut game, *entity, position, None, &colors, &structure,
+                );
+            }
+        }
+        if let Some((entity, position)) = slots
+            .iter()
+            .find(|(_, position)| game.code[position.col as usize].is_none())
+        {
+            game.autoplayed = true;
+            let color = guess[position.col as usize];
+            place_piece(
+                &mut cmd, &mut game, *entity, position, color, &colors, &structure,
+            );
+        }
+        if game
+            .code
+            .iter()
+            .zip(guess.iter())
+            .all(|(slot, color)| *slot == Some(*color))
+        {
+            game.confirmed = true;
+            game.submitted = true;
+        }
+    }
 }
 
-fn play_code(
+pub struct HintEvent;
+
+fn hint_system(
     mut cmd: Commands,
+    mut events: EventReader<HintEvent>,
+    mastermind: Res<Mastermind>,
     mut game: ResMut<MState>,
     colors: Res<MastermindColors>,
     structure: Res<Structure>,
-    squery: Query<(&Select, &mut Selector)>,
-    query: Query<(Entity, &Selectable, &Position)>,
+    query: Query<(Entity, &Position), With<Selectable>>,
 ) {
-    squery.for_each_mut(|(select, mut selector)| {
-        if selector.selected {
-            query.for_each_mut(|(entity, selectable, position)| {
-                if selectable.is_selected(&selector.position) {
-                    if position.row == game.row as u32 {
-                        game.code[position.col as usize] = match game.code[position.col as usize] {
-                            Some(_) => {
-                                cmd.entity(entity).remove_bundle::<ShapeBundle>();
-                                Some(select.piece.color.clone())
-                            }
-                            None => Some(select.piece.color.clone()),
-                        };
-                        let local_translation = Vec3::new(
-                            (position.col as f32) * structure.piece_size,
-                            (position.row as f32) * structure.piece_size,
-                            0.,
-                        ) + structure.boardgame_position;
-                        cmd.entity(entity).insert_bundle(MSB::build_piece(
-                            Transform {
-                                translation: local_translation,
-                                ..Transform::default()
-                            },
-                            colors.pieces_colors[select.piece.color.value()],
-                            structure.piece_size,
-                        ));
-                    }
-                    selector.selected = false;
-                }
-            });
+    if events.iter().count() == 0 {
+        return;
+    }
+    let tries = match mastermind.state.state() {
+        engine::State::Playable(playable) => playable.tries,
+        engine::State::Finish(_) => return,
+    };
+    let candidates = candidates(mastermind.state.rules(), &tries);
+    let matching: Vec<&engine::Code> = candidates
+        .iter()
+        .filter(|code| {
+            game.code
+                .iter()
+                .zip(code.iter())
+                .all(|(placed, color)| placed.map_or(true, |placed| placed == *color))
+        })
+        .collect();
+    let pool: Vec<&engine::Code> = if matching.is_empty() {
+        candidates.iter().collect()
+    } else {
+        matching
+    };
+    if pool.is_empty() {
+        return;
+    }
+    let hint = pool[fastrand::usize(..pool.len())].clone();
+    game.hints += 1;
+    let row = game.row;
+    query.for_each(|(entity, position)| {
+        if position.row == row as u32 {
+            place_piece(
+                &mut cmd,
+                &mut game,
+                entity,
+                position,
+                hint[position.col as usize],
+                &colors,
+                &structure,
+            );
+        }
+    });
+}
+
+fn alert_system(mut cmd: Commands, time: Res<Time>, query: Query<(Entity, &mut Alert)>) {
+    query.for_each_mut(|(entity, mut alert)| {
+        if alert.timer.tick(time.delta()).finished() {
+            cmd.entity(entity).despawn();
         }
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn game_update(
     mut cmd: Commands,
     mut mastermind: ResMut<Mastermind>,
     mut state: ResMut<MState>,
     structure: Res<Structure>,
     colors: Res<MastermindColors>,
+    rules: Res<GameRules>,
+    assets: Res<MAssets>,
+    options: Res<PlayOptions>,
     query: Query<(Entity, &Selectable)>,
+    remaining_query: Query<&mut Text, With<Remaining>>,
+    mut stats: ResMut<Stats>,
     mut snapshots: ResMut<Snapshots>,
 ) {
     let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
-    if is_all_some(&state.code) {
-        match mastermind.state.play(some_code_to_code(&state.code)) {
-            mastermind::State::Playable(playable) => {
-                if state.row != playable.tries.len() {
-                    if let Some(tr) = playable.tries.last() {
-                        let initial_position = structure.boardgame_position
-                            + Vec3::new(
-                                -(structure.piece_size / 4.),
-                                -(structure.piece_size / 4.),
-                                0.,
-                            );
-                        let local_translation = Vec3::new(
-                            structure.piece_size * 4.,
-                            state.row as f32 * structure.piece_size,
-                            0.,
-                        ) + initial_position;
-                        let mut i = 0;
-                        for _ in 0..tr.good {
-                            let tmp = Vec3::new(
-                                (i % 2) as f32 * (structure.piece_size / 2.),
-                                (i / 2) as f32 * (structure.piece_size / 2.),
-                                0.,
-                            ) + local_translation;
-                            entities.push(
-                                cmd.spawn()
-                                    .insert_bundle(MSB::build_result(
-                                        Transform {
-                                            translation: tmp,
-                                            ..Transform::default()
-                                        },
-                                        colors.result_good_colors,
-                                        structure.piece_size,
-                                    ))
-                                    .id(),
-                            );
-                            i += 1;
-                        }
-                        for _ in 0..tr.bad {
-                            let tmp = Vec3::new(
-                                (i % 2) as f32 * (structure.piece_size / 2.),
-                                (i / 2) as f32 * (structure.piece_size / 2.),
-                                0.,
-                            ) + local_translation;
-                            entities.push(
-                                cmd.spawn()
-                                    .insert_bundle(MSB::build_result(
-                                        Transform {
-                                            translation: tmp,
-                                            ..Transform::default()
-                                        },
-                                        colors.result_bad_colors,
-                                        structure.piece_size,
-                                    ))
-                                    .id(),
-                            );
-                            i += 1;
-                        }
-                    }
-
-                    state.row = playable.tries.len();
-                    state.code = [Option::None; 4];
-                }
+    if is_all_some(&state.code) && state.is_ready(options.confirm_submit) && state.warned.is_none()
+    {
+        let tries = match mastermind.state.play(some_code_to_code(&state.code)) {
+            engine::State::Playable(playable) => playable.tries,
+            engine::State::Finish(finish) => {
+                finish_game(
+                    &mut cmd,
+                    entities,
+                    &finish,
+                    &query,
+                    state.autoplayed,
+                    &mut stats,
+                    &rules,
+                    &structure,
+                    &colors,
+                );
+                finish.tries
             }
-            mastermind::State::Finish(finish) => {
-                query.for_each(|(entity, _)| {
-                    cmd.entity(entity).remove::<Selectable>();
-                });
-                for col in 0..4 {
-                    let initial_position = structure.boardgame_position;
-                    let local_translation = Vec3::new(
-                        (col as f32) * structure.piece_size,
-                        structure.piece_size * 10.,
-                        0.,
-                    ) + initial_position;
-                    entities.push(
-                        cmd.spawn()
-                            .insert_bundle(MSB::build_piece(
-                                Transform {
-                                    translation: local_translation,
-                                    ..Transform::default()
+        };
+        if state.row != tries.len() {
+            let remaining = draw_try(
+                &mut cmd, entities, state.row, &tries, &rules, &structure, &colors, &assets,
+            );
+            remaining_query.for_each_mut(|mut text| {
+                text.sections[0].value = remaining.to_string();
+            });
+            state.row = tries.len();
+        }
+        state.clear_code();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn finish_game(
+    cmd: &mut Commands,
+    entities: &mut Vec<Entity>,
+    finish: &engine::Finish,
+    query: &Query<(Entity, &Selectable)>,
+    autoplayed: bool,
+    stats: &mut Stats,
+    rules: &GameRules,
+    structure: &Structure,
+    colors: &MastermindColors,
+) {
+    query.for_each(|(entity, _)| {
+        cmd.entity(entity).remove::<Selectable>();
+    });
+    if !autoplayed {
+        stats.record(finish.win, finish.tries.len());
+        stats.save();
+    }
+    for col in 0..rules.code_length {
+        let local_translation =
+            Vec3::new((col as f32) * structure.piece_size, 0., 0.) + structure.secrets_position;
+        entities.push(
+            cmd.spawn()
+                .insert_bundle(MSB::build_piece(
+                    Transform {
+                        translation: local_translation,
+                        ..Transform::default()
+                    },
+                    colors.pieces_colors[finish.code[col].value()],
+                    structure.piece_size,
+                ))
+                .id(),
+        );
+    }
+}
+
+fn clock_system(
+    mut clock: ResMut<Clock>,
+    state: Res<MState>,
+    options: Res<PlayOptions>,
+    time: Res<Time>,
+    query: Query<&mut Text, With<ClockText>>,
+) {
+    let now = time.seconds_since_startup();
+    if state.row > 0 || state.code.iter().any(Option::is_some) {
+        clock.start(state.row, now);
+    }
+    clock.next_row(state.row, now);
+    let value = match clock.remaining(options.blitz, now) {
+        Some(remaining) => format_time(remaining.ceil()),
+        None => format_time(clock.elapsed(now)),
+    };
+    query.for_each_mut(|mut text| {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn blitz_system(
+    mut cmd: Commands,
+    mut mastermind: ResMut<Mastermind>,
+    mut state: ResMut<MState>,
+    mut clock: ResMut<Clock>,
+    time: Res<Time>,
+    options: Res<PlayOptions>,
+    rules: Res<GameRules>,
+    structure: Res<Structure>,
+    colors: Res<MastermindColors>,
+    query: Query<(Entity, &Selectable)>,
+    mut stats: ResMut<Stats>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let now = time.seconds_since_startup();
+    if clock
+        .remaining(options.blitz, now)
+        .map_or(true, |left| left > 0.)
+    {
+        return;
+    }
+    if let engine::State::Finish(_) = mastermind.state.state() {
+        return;
+    }
+    if let engine::State::Finish(finish) = mastermind.state.time_out() {
+        clock.stop(now);
+        let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+        finish_game(
+            &mut cmd,
+            entities,
+            &finish,
+            &query,
+            state.autoplayed,
+            &mut stats,
+            &rules,
+            &structure,
+            &colors,
+        );
+        state.clear_code();
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn reorient_system(
+    mut cmd: Commands,
+    mastermind: Res<Mastermind>,
+    mut state: ResMut<MState>,
+    mut structure: ResMut<Structure>,
+    options: Res<PlayOptions>,
+    window: Res<WindowDescriptor>,
+    rules: Res<GameRules>,
+    colors: Res<MastermindColors>,
+    assets: Res<MAssets>,
+    ui: Query<Entity, Or<(With<Hud>, With<Camera>)>>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let size = (window.width, window.height);
+    let landscape = options.layout.is_landscape(size);
+    if landscape == structure.landscape {
+        return;
+    }
+    if let engine::State::Finish(_) = mastermind.state.state() {
+        return;
+    }
+    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+    entities.retain(|entity| {
+        let keep = ui.get(*entity).is_ok();
+        if !keep {
+            cmd.entity(*entity).despawn_recursive();
+        }
+        keep
+    });
+    *structure = Structure::new(size, &rules, landscape);
+    spawn_background(&mut cmd, entities, &colors, &structure, &rules, &assets);
+    spawn_pieces(&mut cmd, entities, &colors, &structure, &rules);
+    if state.warned.is_some() {
+        state.warned = None;
+    }
+    cmd.insert_resource(Restore);
+}
+
+struct GameOver;
+
+#[allow(clippy::too_many_arguments)]
+fn game_over_system(
+    mut cmd: Commands,
+    mastermind: Res<Mastermind>,
+    state: Res<MState>,
+    mut clock: ResMut<Clock>,
+    mut scores: ResMut<SessionScores>,
+    time: Res<Time>,
+    rules: Res<GameRules>,
+    structure: Res<Structure>,
+    colors: Res<MastermindColors>,
+    button_materials: Res<ButtonMaterials>,
+    assets: Res<MAssets>,
+    difficulty: Res<Difficulty>,
+    highscores: Res<HighScores>,
+    query: Query<Entity, With<GameOver>>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let finish = match mastermind.state.state() {
+        engine::State::Finish(finish) => finish,
+        engine::State::Playable(_) => return,
+    };
+    if query.iter().next().is_some() {
+        return;
+    }
+    clock.stop(time.seconds_since_startup());
+    let seconds = clock.elapsed(time.seconds_since_startup());
+    let score = Score::compute(
+        finish.win,
+        finish.tries.len(),
+        rules.max_attempts,
+        seconds,
+        state.hints,
+        state.autoplayed,
+    );
+    scores.push(score);
+    let record = highscores.is_record(*difficulty, score.points);
+    if record {
+        cmd.insert_resource(NameEntry {
+            difficulty: *difficulty,
+            score,
+            name: String::new(),
+        });
+    }
+    let (title, color) = if finish.win {
+        ("you win!", Color::GOLD)
+    } else {
+        ("you lose", Color::hex("F51000").unwrap())
+    };
+    let text_style = |font_size: f32, color: Color| TextStyle {
+        font: assets.font.clone(),
+        font_size,
+        color,
+    };
+    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+    entities.push(
+        cmd.spawn_bundle(small_text(
+            format!("{} pts", score.points),
+            Vec3::new(
+                structure.piece_size * (rules.code_length + 1) as f32,
+                -structure.piece_size * 0.32,
+                0.,
+            ) + structure.secrets_position,
+            &structure,
+            &colors,
+            &assets,
+        ))
+        .id(),
+    );
+    entities.push(
+        cmd.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(
+                    Val::Percent(80.0),
+                    Val::Px(if record { 220.0 } else { 190.0 }),
+                ),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Percent(10.0),
+                    top: Val::Percent(35.0),
+                    ..Default::default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.overlay.clone(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text {
+                    sections: vec![
+                        TextSection {
+                            value: format!("{}\n", title),
+                            style: text_style(28.0, color),
+                        },
+                        TextSection {
+                            value: format!(
+                                "rows used {} / {}\ntime {}\nscore {} - session best {}",
+                                finish.tries.len(),
+                                rules.max_attempts,
+                                format_time(seconds),
+                                score.points,
+                                scores.best().unwrap_or(0)
+                            ),
+                            style: text_style(16.0, Color::rgb(0.9, 0.9, 0.9)),
+                        },
+                    ],
+                    alignment: TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        vertical: VerticalAlign::Center,
+                    },
+                },
+                ..Default::default()
+            });
+            if record {
+                parent
+                    .spawn_bundle(TextBundle {
+                        text: Text {
+                            sections: vec![
+                                TextSection {
+                                    value: "new record! name ".to_string(),
+                                    style: text_style(16.0, Color::GOLD),
+                                },
+                                TextSection {
+                                    value: String::new(),
+                                    style: text_style(16.0, Color::WHITE),
+                                },
+                                TextSection {
+                                    value: "_".to_string(),
+                                    style: text_style(16.0, Color::GOLD),
                                 },
-                                colors.pieces_colors[finish.code[col].value()],
-                                structure.piece_size,
-                            ))
-                            .id(),
+                            ],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .insert(NameInput);
+            }
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(36.0)),
+                        justify_content: JustifyContent::SpaceEvenly,
+                        ..Default::default()
+                    },
+                    material: button_materials.transparent.clone(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_action_button(
+                        parent,
+                        ActionButton::new(GameAction::Restart, "play again", "PLAY AGAIN"),
+                        button_materials.normal.clone(),
+                        Val::Percent(45.0),
+                        &assets,
                     );
-                }
-                
+                    spawn_action_button(
+                        parent,
+                        ActionButton::new(GameAction::Menu, "menu", "MENU"),
+                        button_materials.normal.clone(),
+                        Val::Percent(45.0),
+                        &assets,
+                    );
+                });
+        })
+        .insert(GameOver)
+        .id(),
+    );
+}
+
+struct NameInput;
+
+#[allow(clippy::too_many_arguments)]
+fn name_entry_system(
+    mut cmd: Commands,
+    name_entry: Option<ResMut<NameEntry>>,
+    mut highscores: ResMut<HighScores>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut pad_events: EventReader<PadEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    bindings: Res<Bindings>,
+    query: Query<&mut Text, With<NameInput>>,
+) {
+    let typed: Vec<char> = characters.iter().map(|event| event.char).collect();
+    let accepted = pad_events.iter().any(|event| *event == PadEvent::Accept);
+    let mut entry = match name_entry {
+        Some(entry) => entry,
+        None => return,
+    };
+    let pressed = |action: Action| {
+        bindings
+            .keys(action)
+            .iter()
+            .any(|key| keyboard.just_pressed(*key))
+    };
+    let (erase, submit) = (pressed(Action::Clear), pressed(Action::Submit));
+    if !(erase || submit) {
+        for c in typed {
+            if !c.is_control() && entry.name.chars().count() < NAME_LENGTH {
+                entry.name.push(c);
             }
         }
     }
+    if erase {
+        entry.name.pop();
+    }
+    if accepted || submit {
+        entry.commit(&mut highscores);
+        cmd.remove_resource::<NameEntry>();
+        query.for_each_mut(|mut text| {
+            text.sections[0].value = "record saved".to_string();
+            text.sections[1].value.clear();
+            text.sections[2].value.clear();
+        });
+    } else if entry.is_changed() {
+        query.for_each_mut(|mut text| {
+            text.sections[1].value = entry.name.clone();
+        });
+    }
 }
 
-struct ActionButton {
-    pub clicked: fn(&mut ResMut<State<AppState>>),
+fn save_name_entry(
+    mut cmd: Commands,
+    name_entry: Option<Res<NameEntry>>,
+    mut highscores: ResMut<HighScores>,
+) {
+    if let Some(entry) = name_entry {
+        entry.commit(&mut highscores);
+        cmd.remove_resource::<NameEntry>();
+    }
+}
+
+fn save_game(mastermind: Res<Mastermind>, state: Res<MState>, clock: Res<Clock>, time: Res<Time>) {
+    SavedGame::store(
+        &mastermind.state,
+        &state,
+        &clock,
+        time.seconds_since_startup(),
+    );
+}
+
+fn autosave_system(
+    mastermind: Res<Mastermind>,
+    state: Res<MState>,
+    clock: Res<Clock>,
+    time: Res<Time>,
+) {
+    if state.is_changed() && !state.is_added() {
+        SavedGame::store(
+            &mastermind.state,
+            &state,
+            &clock,
+            time.seconds_since_startup(),
+        );
+    }
+}
+
+fn save_on_close(
+    mut closed: EventReader<WindowCloseRequested>,
+    app_state: Res<State<AppState>>,
+    mastermind: Option<Res<Mastermind>>,
+    state: Option<Res<MState>>,
+    clock: Option<Res<Clock>>,
+    time: Res<Time>,
+) {
+    if closed.iter().next().is_none() {
+        return;
+    }
+    let in_game = *app_state.current() == AppState::InGame
+        || app_state.inactives().contains(&AppState::InGame);
+    if let (true, Some(mastermind), Some(state), Some(clock)) = (in_game, mastermind, state, clock)
+    {
+        SavedGame::store(
+            &mastermind.state,
+            &state,
+            &clock,
+            time.seconds_since_startup(),
+        );
+    }
+}
+
+struct Restore;
+
+#[allow(clippy::too_many_arguments)]
+fn restore_system(
+    mut cmd: Commands,
+    restore: Option<Res<Restore>>,
+    mastermind: Res<Mastermind>,
+    state: Res<MState>,
+    structure: Res<Structure>,
+    colors: Res<MastermindColors>,
+    rules: Res<GameRules>,
+    assets: Res<MAssets>,
+    slots: Query<(Entity, &Position)>,
+    remaining_query: Query<&mut Text, With<Remaining>>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    if restore.is_none() {
+        return;
+    }
+    let tries = match mastermind.state.state() {
+        engine::State::Playable(playable) => playable.tries,
+        engine::State::Finish(finish) => finish.tries,
+    };
+    for (entity, position) in slots.iter() {
+        let (row, col) = (position.row as usize, position.col as usize);
+        let color = match tries.get(row) {
+            Some(tr) => Some(tr.code[col]),
+            None if row == state.row => state.code[col],
+            None => None,
+        };
+        if let Some(color) = color {
+            cmd.entity(entity)
+                .insert_bundle(slot_piece(position, color, &colors, &structure));
+        }
+    }
+    let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+    for row in 0..tries.len() {
+        let remaining = draw_try(
+            &mut cmd,
+            entities,
+            row,
+            &tries[..=row],
+            &rules,
+            &structure,
+            &colors,
+            &assets,
+        );
+        remaining_query.for_each_mut(|mut text| {
+            text.sections[0].value = remaining.to_string();
+        });
+    }
+    cmd.remove_resource::<Restore>();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_try(
+    cmd: &mut Commands,
+    entities: &mut Vec<Entity>,
+    row: usize,
+    tries: &[engine::Try],
+    rules: &GameRules,
+    structure: &Structure,
+    colors: &MastermindColors,
+    assets: &MAssets,
+) -> usize {
+    if let Some(tr) = tries.last() {
+        let local_translation = Vec3::new(
+            structure.piece_size * rules.code_length as f32,
+            row as f32 * structure.piece_size,
+            0.,
+        ) + structure.boardgame_position;
+        let results = std::iter::repeat(colors.result_good_colors)
+            .take(tr.good)
+            .chain(std::iter::repeat(colors.result_bad_colors).take(tr.bad));
+        for (i, result_colors) in results.enumerate() {
+            let (offset, size) = result_layout(i, rules.code_length, structure.piece_size);
+            entities.push(
+                cmd.spawn()
+                    .insert_bundle(MSB::build_result(
+                        Transform {
+                            translation: local_translation + offset,
+                            ..Transform::default()
+                        },
+                        result_colors,
+                        size,
+                    ))
+                    .id(),
+            );
+        }
+    }
+    if let (Some(tr), Some(translation)) = (tries.last(), structure.history_entry(row)) {
+        let step = structure.piece_size * HISTORY_SCALE;
+        for (col, color) in tr.code.iter().enumerate() {
+            entities.push(
+                cmd.spawn_bundle(MSB::build_piece(
+                    Transform {
+                        translation: translation + Vec3::new(col as f32 * step, 0., 0.),
+                        ..Transform::default()
+                    },
+                    colors.pieces_colors[color.value()],
+                    step,
+                ))
+                .id(),
+            );
+        }
+        entities.push(
+            cmd.spawn_bundle(history_text(
+                tr,
+                translation + Vec3::new((rules.code_length as f32 + 2.) * step, 0., 0.),
+                structure,
+                colors,
+                assets,
+            ))
+            .id(),
+        );
+    }
+    let remaining = candidates(rules, tries).len();
+    entities.push(
+        cmd.spawn_bundle(remaining_text(
+            remaining,
+            Vec3::new(
+                structure.piece_size * (rules.code_length + 1) as f32,
+                row as f32 * structure.piece_size,
+                0.,
+            ) + structure.boardgame_position,
+            structure,
+            colors,
+            assets,
+        ))
+        .id(),
+    );
+    remaining
+}
+
+struct SubmitMarker;
+
+#[allow(clippy::too_many_arguments)]
+fn submit_marker_system(
+    mut cmd: Commands,
+    state: Res<MState>,
+    options: Res<PlayOptions>,
+    rules: Res<GameRules>,
+    structure: Res<Structure>,
+    colors: Res<MastermindColors>,
+    query: Query<Entity, With<SubmitMarker>>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let expected = options.confirm_submit
+        && state.row < rules.max_attempts
+        && is_all_some(&state.code)
+        && !state.submitted;
+    let shown = query.iter().next().is_some();
+    if expected && !shown {
+        let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+        entities.push(
+            cmd.spawn_bundle(MSB::build_result(
+                Transform {
+                    translation: Vec3::new(
+                        structure.piece_size * rules.code_length as f32,
+                        state.row as f32 * structure.piece_size,
+                        1.,
+                    ) + structure.boardgame_position,
+                    ..Transform::default()
+                },
+                colors.submit_colors,
+                structure.piece_size * 1.5,
+            ))
+            .insert(SubmitMarker)
+            .id(),
+        );
+    } else if !expected && shown {
+        query.for_each(|entity| {
+            cmd.entity(entity).despawn();
+        });
+    }
+}
+
+struct Warning;
+
+#[allow(clippy::too_many_arguments)]
+fn warning_system(
+    mut cmd: Commands,
+    mastermind: Res<Mastermind>,
+    mut state: ResMut<MState>,
+    options: Res<PlayOptions>,
+    structure: Res<Structure>,
+    colors: Res<MastermindColors>,
+    button_materials: Res<ButtonMaterials>,
+    assets: Res<MAssets>,
+    warning_query: Query<Entity, With<Warning>>,
+    mut snapshots: ResMut<Snapshots>,
+) {
+    let code = some_code_to_code(&state.code);
+    let found = match mastermind.state.state() {
+        engine::State::Playable(playable)
+            if options.warn_inconsistent
+                && !state.confirmed
+                && is_all_some(&state.code)
+                && state.is_ready(options.confirm_submit) =>
+        {
+            contradiction(&code, &playable.tries).map(|found| (found, playable.tries))
+        }
+        _ => None,
+    };
+    let warned = found.as_ref().map(|_| code);
+    if state.warned == warned {
+        return;
+    }
+    warning_query.for_each(|entity| {
+        cmd.entity(entity).despawn_recursive();
+    });
+    state.warned = warned;
+    if let Some(((index, (good, bad)), tries)) = found {
+        let entities = snapshots.get_mut_snap(&String::from("Game")).unwrap();
+        let code_length = state.code.len() as f32;
+        let row_position = structure.boardgame_position
+            + Vec3::new(0., state.row as f32 * structure.piece_size, 0.);
+        entities.push(
+            cmd.spawn_bundle(MSB::build_outlined_rect(
+                Transform {
+                    translation: row_position
+                        + Vec3::new((code_length - 1.) * structure.piece_size / 2., 0., 1.),
+                    ..Transform::default()
+                },
+                colors.warning_colors,
+                structure.piece_size * code_length,
+                structure.piece_size,
+                structure.piece_size * 0.1,
+            ))
+            .insert(Warning)
+            .id(),
+        );
+        let tooltip = structure.screen_position(
+            row_position + Vec3::new(-structure.piece_size / 2., structure.piece_size / 2., 0.),
+        );
+        let text_style = TextStyle {
+            font: assets.font.clone(),
+            font_size: 14.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        };
+        entities.push(
+            cmd.spawn_bundle(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        left: Val::Px(tooltip.x),
+                        bottom: Val::Px(tooltip.y),
+                        ..Default::default()
+                    },
+                    align_items: AlignItems::Center,
+                    padding: Rect::all(Val::Px(4.0)),
+                    ..Default::default()
+                },
+                material: button_materials.alerte.clone(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        format!(
+                            "try {} gave {}/{}, not {}/{} ",
+                            index + 1,
+                            tries[index].good,
+                            tries[index].bad,
+                            good,
+                            bad
+                        ),
+                        text_style.clone(),
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                });
+                parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(50.0), Val::Px(22.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        material: button_materials.normal.clone(),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section("play", text_style, Default::default()),
+                            ..Default::default()
+                        });
+                    })
+                    .insert(ActionButton::new(GameAction::Confirm, "play", "PLAY"));
+            })
+            .insert(Warning)
+            .id(),
+        );
+    }
+}
+
+fn result_layout(index: usize, code_length: usize, piece_size: f32) -> (Vec3, f32) {
+    let per_line = (code_length as f32).sqrt().ceil().max(2.) as usize;
+    let step = piece_size / per_line as f32;
+    let start = (step - piece_size) / 2.;
+    (
+        Vec3::new(
+            start + (index % per_line) as f32 * step,
+            start + (index / per_line) as f32 * step,
+            0.,
+        ),
+        step * 2.,
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameAction {
+    Pause,
+    Menu,
+    Restart,
+    AutoPlay,
+    Hint,
+    Confirm,
+    Undo,
+    ClearRow,
+}
+
+pub struct ActionButton {
+    pub action: GameAction,
+    pub label: &'static str,
+    pub hovered_label: &'static str,
 }
 
 impl ActionButton {
-    fn new(clicked: fn(&mut ResMut<State<AppState>>)) -> Self {
-        Self { clicked }
+    fn new(action: GameAction, label: &'static str, hovered_label: &'static str) -> Self {
+        Self {
+            action,
+            label,
+            hovered_label,
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn button_system(
     button_materials: Res<ButtonMaterials>,
     mut interaction_query: Query<
@@ -450,23 +1663,78 @@ fn button_system(
         (Changed<Interaction>, With<Button>),
     >,
     mut app_state: ResMut<State<AppState>>,
-    
+    mut autoplay: ResMut<AutoPlay>,
+    mut hints: EventWriter<HintEvent>,
+    mut edits: EventWriter<EditEvent>,
+    mut state: ResMut<MState>,
     mut text_query: Query<&mut Text>,
 ) {
-    for (interaction, mut material, action, children) in interaction_query.iter_mut() {
+    for (interaction, mut material, button, children) in interaction_query.iter_mut() {
         let mut text = text_query.get_mut(children[0]).unwrap();
         match *interaction {
-            Interaction::Clicked => {
-                (action.clicked)(&mut app_state);
-            }
+            Interaction::Clicked => run_action(
+                button.action,
+                &mut app_state,
+                &mut autoplay,
+                &mut hints,
+                &mut edits,
+                &mut state,
+            ),
             Interaction::Hovered => {
-                text.sections[0].value = "MENU".to_string();
+                text.sections[0].value = button.hovered_label.to_string();
                 *material = button_materials.hovered.clone();
             }
             Interaction::None => {
-                text.sections[0].value = "  ...  ".to_string();
-                *material = button_materials.normal.clone();
+                text.sections[0].value = button.label.to_string();
+                *material = action_material(button.action, &autoplay, &button_materials);
             }
         }
     }
 }
+
+pub fn run_action(
+    action: GameAction,
+    app_state: &mut State<AppState>,
+    autoplay: &mut AutoPlay,
+    hints: &mut EventWriter<HintEvent>,
+    edits: &mut EventWriter<EditEvent>,
+    state: &mut MState,
+) {
+    match action {
+        GameAction::Pause => {
+            app_state.push(AppState::Paused).unwrap();
+        }
+        GameAction::Menu => {
+            app_state.pop().unwrap();
+        }
+        GameAction::Restart => {
+            app_state.set(AppState::Restart).unwrap();
+        }
+        GameAction::AutoPlay => {
+            autoplay.enabled = !autoplay.enabled;
+        }
+        GameAction::Hint => {
+            hints.send(HintEvent);
+        }
+        GameAction::Confirm => {
+            state.confirmed = true;
+        }
+        GameAction::Undo => {
+            edits.send(EditEvent::Undo);
+        }
+        GameAction::ClearRow => {
+            edits.send(EditEvent::ClearRow);
+        }
+    }
+}
+
+pub fn action_material(
+    action: GameAction,
+    autoplay: &AutoPlay,
+    button_materials: &ButtonMaterials,
+) -> Handle<ColorMaterial> {
+    match action {
+        GameAction::AutoPlay if autoplay.enabled => button_materials.selected.clone(),
+        _ => button_materials.normal.clone(),
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+
+const STICK_THRESHOLD: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadEvent {
+    Up,
+    Down,
+    Left,
+    Right,
+    Accept,
+    Cancel,
+    Start,
+}
+
+#[derive(Default)]
+struct PadTracker {
+    gamepads: Vec<Gamepad>,
+    direction: (i8, i8),
+}
+
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_event::<PadEvent>()
+            .add_system(gamepad_system.system());
+    }
+}
+
+fn gamepad_system(
+    mut tracker: Local<PadTracker>,
+    mut connections: EventReader<GamepadEvent>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut events: EventWriter<PadEvent>,
+) {
+    for GamepadEvent(gamepad, event_type) in connections.iter() {
+        match event_type {
+            GamepadEventType::Connected => {
+                if !tracker.gamepads.contains(gamepad) {
+                    tracker.gamepads.push(*gamepad);
+                }
+            }
+            GamepadEventType::Disconnected => {
+                tracker.gamepads.retain(|pad| pad != gamepad);
+            }
+            _ => {}
+        }
+    }
+
+    for GamepadButton(_, button_type) in buttons.get_just_pressed() {
+        let event = match button_type {
+            GamepadButtonType::DPadUp => PadEvent::Up,
+            GamepadButtonType::DPadDown => PadEvent::Down,
+            GamepadButtonType::DPadLeft => PadEvent::Left,
+            GamepadButtonType::DPadRight => PadEvent::Right,
+            GamepadButtonType::South => PadEvent::Accept,
+            GamepadButtonType::East => PadEvent::Cancel,
+            GamepadButtonType::Start => PadEvent::Start,
+            _ => continue,
+        };
+        events.send(event);
+    }
+
+    let read = |gamepad: Gamepad, stick: GamepadAxisType, dpad: GamepadAxisType| {
+        let stick = axes.get(GamepadAxis(gamepad, stick)).unwrap_or(0.);
+        let dpad = axes.get(GamepadAxis(gamepad, dpad)).unwrap_or(0.);
+        if stick.abs() > dpad.abs() {
+            stick
+        } else {
+            dpad
+        }
+    };
+    let (x, y) = tracker
+        .gamepads
+        .iter()
+        .map(|gamepad| {
+            (
+                read(
+                    *gamepad,
+                    GamepadAxisType::LeftStickX,
+                    GamepadAxisType::DPadX,
+                ),
+                read(
+                    *gamepad,
+                    GamepadAxisType::LeftStickY,
+                    GamepadAxisType::DPadY,
+                ),
+            )
+        })
+        .find(|(x, y)| x.abs() > STICK_THRESHOLD || y.abs() > STICK_THRESHOLD)
+        .unwrap_or((0., 0.));
+    let direction = if x.abs() > STICK_THRESHOLD && x.abs() >= y.abs() {
+        (x.signum() as i8, 0)
+    } else if y.abs() > STICK_THRESHOLD {
+        (0, y.signum() as i8)
+    } else {
+        (0, 0)
+    };
+    if direction != tracker.direction {
+        match direction {
+            (1, _) => events.send(PadEvent::Right),
+            (-1, _) => events.send(PadEvent::Left),
+            (_, 1) => events.send(PadEvent::Up),
+            (_, -1) => events.send(PadEvent::Down),
+            _ => {}
+        }
+        tracker.direction = direction;
+    }
+}
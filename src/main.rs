@@ -1,39 +1,79 @@
+use action::ActionPlugin;
 use bevy::input::ElementState;
 use bevy::prelude::*;
-use bevy::window::WindowMode;
+use bevy::window::WindowResized;
 use bevy_prototype_lyon::plugin::ShapePlugin;
+use controls::ControlsPlugin;
+use gamepad::GamepadPlugin;
 use loading::LoadingPlugin;
 use menu::MenuPlugin;
+use pause::PausePlugin;
+use scores::ScoresPlugin;
+use settings::SettingsPlugin;
+use stats::StatsPlugin;
 
 mod component;
+mod engine;
 mod mastermind_shape_bundler;
 mod resource;
+mod solver;
+mod storage;
 
+mod action;
+mod controls;
 mod game;
+mod gamepad;
 mod loading;
 mod menu;
+mod pause;
+mod scores;
+mod screen;
+mod settings;
 mod state;
+mod stats;
 
 use crate::state::AppState;
 use game::GamePlugin;
-use resource::{snapshots::Snapshots, structure::Structure};
+use resource::{
+    display::{DisplayMode, DisplaySettings},
+    options::PlayOptions,
+    rules::{Difficulty, GameRules},
+    settings::{Settings, SettingsError},
+    snapshots::Snapshots,
+    structure::Structure,
+};
 
-use component::{mouse::MouseState, selector::Selector};
+use component::{
+    camera::BoardCamera, mouse::MouseState, selectable::Selectable, selector::Selector,
+    touch::TouchState,
+};
 
 use resource::color::MastermindColors;
 
 fn main() {
+    let (settings, error) = match Settings::load() {
+        Ok(settings) => (settings, None),
+        Err(err) => (Settings::default(), Some(err)),
+    };
     App::build()
-        .insert_resource(Msaa { samples: 8 })
+        .insert_resource(SettingsError(error))
+        .insert_resource(Msaa {
+            samples: settings.display.msaa,
+        })
         .insert_resource(WindowDescriptor {
             title: "MasterMind".to_string(),
-            width: 300.,
-            height: 600.,
-            resizable: false,
-            vsync: true,
-            mode: WindowMode::Windowed,
+            width: settings.window_width,
+            height: settings.window_height,
+            resizable: true,
+            vsync: settings.display.vsync,
+            mode: settings.display.mode.window_mode(),
             ..Default::default()
         })
+        .insert_resource(settings.display)
+        .insert_resource(settings.difficulty)
+        .insert_resource(settings.difficulty.rules())
+        .insert_resource(settings.options())
+        .insert_resource(settings)
         .init_resource::<Snapshots>()
         .add_plugins(DefaultPlugins)
         .add_plugin(ShapePlugin)
@@ -41,10 +81,22 @@ fn main() {
         .init_resource::<MastermindColors>()
         .add_state(AppState::Menu)
         .add_startup_system(setup.system())
+        .add_startup_system(report_settings_error.system())
+        .add_system(relayout.system().label("Relayout"))
+        .add_system(update_camera.system().after("Relayout"))
+        .add_system(apply_display.system())
+        .add_system(save_settings.system())
         .add_system_set(SystemSet::on_enter(AppState::Restart).with_system(restart.system()))
+        .add_plugin(ActionPlugin)
+        .add_plugin(GamepadPlugin)
         .add_plugin(LoadingPlugin)
         .add_plugin(MenuPlugin)
         .add_plugin(GamePlugin)
+        .add_plugin(ControlsPlugin)
+        .add_plugin(PausePlugin)
+        .add_plugin(SettingsPlugin)
+        .add_plugin(StatsPlugin)
+        .add_plugin(ScoresPlugin)
         .run();
 }
 
@@ -55,22 +107,123 @@ pub fn setup(
     window: Res<WindowDescriptor>,
 ) {
     let mut camera = OrthographicCameraBundle::new_2d();
-    camera.transform = Transform::from_xyz(
-        (window.width - structure.piece_size) / 2.,
-        (window.height - structure.piece_size) / 2.,
-        0.,
-    );
+    camera.transform = camera_transform(&structure, &window);
     commands.insert_resource(ClearColor(colors.clear_color));
-    commands.spawn_bundle(camera);
+    commands.spawn_bundle(camera).insert(BoardCamera);
     commands
         .spawn()
         .insert(MouseState {
             state: ElementState::Released,
         })
+        .insert(TouchState { id: None })
         .insert(Selector {
             position: Vec2::default(),
             selected: false,
+            pressed: false,
+        });
+}
+
+fn camera_transform(structure: &Structure, window: &WindowDescriptor) -> Transform {
+    Transform::from_xyz(
+        (window.width - structure.piece_size) / 2.,
+        (window.height - structure.piece_size) / 2.,
+        0.,
+    )
+}
+
+fn update_camera(
+    structure: Res<Structure>,
+    window: Res<WindowDescriptor>,
+    query: Query<&mut Transform, With<BoardCamera>>,
+) {
+    if structure.is_changed() {
+        query.for_each_mut(|mut transform| {
+            let translation = camera_transform(&structure, &window).translation;
+            transform.translation.x = translation.x;
+            transform.translation.y = translation.y;
+        });
+    }
+}
+
+fn report_settings_error(mut commands: Commands, error: Res<SettingsError>) {
+    if let Some(err) = &error.0 {
+        warn!("{}, using default settings", err);
+    }
+    commands.remove_resource::<SettingsError>();
+}
+
+#[allow(clippy::type_complexity)]
+fn relayout(
+    mut resized: EventReader<WindowResized>,
+    mut window: ResMut<WindowDescriptor>,
+    mut structure: ResMut<Structure>,
+    rules: Res<GameRules>,
+    query: Query<&mut Transform, (Without<Node>, Without<Camera>, Without<Parent>)>,
+    selectables: Query<&mut Selectable>,
+) {
+    if let Some(event) = resized.iter().last() {
+        window.width = event.width;
+        window.height = event.height;
+        let layout = Structure::new((event.width, event.height), &rules, structure.landscape);
+        let ratio = layout.piece_size / structure.piece_size;
+        query.for_each_mut(|mut transform| {
+            transform.translation = structure.relayout(transform.translation, &layout);
+            transform.scale *= Vec3::new(ratio, ratio, 1.);
         });
+        selectables.for_each_mut(|mut selectable| {
+            let translation =
+                structure.relayout(structure.world_position(selectable.position()), &layout);
+            selectable.relayout(layout.screen_position(translation), ratio);
+        });
+        *structure = layout;
+    }
+}
+
+fn apply_display(display: Res<DisplaySettings>, mut windows: ResMut<Windows>) {
+    if display.is_changed() {
+        if let Some(window) = windows.get_primary_mut() {
+            window.set_mode(display.mode.window_mode());
+            window.set_scale_factor_override(Some(
+                window.backend_scale_factor() * display.ui_scale as f64,
+            ));
+        }
+    }
+}
+
+fn save_settings(
+    mut settings: ResMut<Settings>,
+    difficulty: Res<Difficulty>,
+    options: Res<PlayOptions>,
+    display: Res<DisplaySettings>,
+    window: Res<WindowDescriptor>,
+) {
+    if !(difficulty.is_changed()
+        || options.is_changed()
+        || display.is_changed()
+        || window.is_changed())
+    {
+        return;
+    }
+    let (window_width, window_height) = match display.mode {
+        DisplayMode::Windowed => (window.width, window.height),
+        _ => (settings.window_width, settings.window_height),
+    };
+    let current = Settings {
+        difficulty: *difficulty,
+        warn_inconsistent: options.warn_inconsistent,
+        confirm_submit: options.confirm_submit,
+        layout: options.layout,
+        blitz: options.blitz,
+        display: *display,
+        window_width,
+        window_height,
+    };
+    if *settings != current {
+        if let Err(err) = current.save() {
+            warn!("{}", err);
+        }
+        *settings = current;
+    }
 }
 
 fn restart(mut state: ResMut<State<AppState>>) {
@@ -54,6 +54,28 @@ pub fn build_secret_case(transform: Transform, colors: (Color, Color), size: f32
         transform,
     )
 }
+pub fn build_outlined_rect(
+    transform: Transform,
+    colors: (Color, Color),
+    width: f32,
+    height: f32,
+    line_width: f32,
+) -> ShapeBundle {
+    let shape = shapes::Rectangle {
+        width,
+        height,
+        ..shapes::Rectangle::default()
+    };
+    GeometryBuilder::build_as(
+        &shape,
+        ShapeColors::outlined(colors.0, colors.1),
+        DrawMode::Outlined {
+            fill_options: FillOptions::default(),
+            outline_options: StrokeOptions::default().with_line_width(line_width),
+        },
+        transform,
+    )
+}
 pub fn build_piece(transform: Transform, colors: (Color, Color, Color), size: f32) -> ShapeBundle {
     let shape = shapes::Circle {
         radius: size * 0.4,
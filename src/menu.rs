@@ -1,10 +1,19 @@
 use crate::mastermind_shape_bundler as MSB;
 use crate::{
+    gamepad::PadEvent,
     loading::AssetsLoading,
     resource::{
-        assets::Assets as MyAssets, button::ButtonMaterials, color::MastermindColors,
-        snapshots::Snapshots, structure::Structure,
+        assets::Assets as MyAssets,
+        button::ButtonMaterials,
+        color::MastermindColors,
+        focus::MenuFocus,
+        options::PlayOptions,
+        rules::{Difficulty, GameRules},
+        savegame::{Resume, SavedGame},
+        snapshots::Snapshots,
+        structure::Structure,
     },
+    settings::SettingsPage,
     state::AppState,
 };
 use bevy::core::FixedTimestep;
@@ -14,6 +23,7 @@ pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<ButtonMaterials>()
+            .init_resource::<MenuFocus>()
             .add_system_set(
                 SystemSet::on_enter(AppState::Menu).with_system(setup.system().label("Setup")),
             )
@@ -25,7 +35,10 @@ impl Plugin for MenuPlugin {
                     .with_system(draw_background.system()),
             )
             .add_system_set(
-                SystemSet::on_update(AppState::Menu).with_system(button_system.system()),
+                SystemSet::on_update(AppState::Menu)
+                    .with_system(button_system.system())
+                    .with_system(gamepad_system.system())
+                    .with_system(difficulty_system.system()),
             )
             .add_system_set(
                 SystemSet::on_update(AppState::Menu)
@@ -52,33 +65,289 @@ fn setup(
     app_state.push(AppState::Loading).unwrap();
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MenuButton {
+    Play,
+    Continue,
+    Blitz,
+    Difficulty(Difficulty),
+    Warnings,
+    Submit,
+    Layout,
+    Controls,
+    Display,
+    Stats,
+    Scores,
+}
+
+impl MenuButton {
+    fn all() -> Vec<MenuButton> {
+        let mut buttons = vec![MenuButton::Play];
+        if SavedGame::exists() {
+            buttons.push(MenuButton::Continue);
+        }
+        buttons.extend(
+            Difficulty::ALL
+                .iter()
+                .map(|choice| MenuButton::Difficulty(*choice)),
+        );
+        buttons.push(MenuButton::Warnings);
+        buttons.push(MenuButton::Submit);
+        buttons.push(MenuButton::Layout);
+        buttons.push(MenuButton::Blitz);
+        buttons.push(MenuButton::Controls);
+        buttons.push(MenuButton::Display);
+        buttons.push(MenuButton::Stats);
+        buttons.push(MenuButton::Scores);
+        buttons
+    }
+
+    fn is_focused(&self, focus: &MenuFocus) -> bool {
+        focus
+            .index
+            .map_or(false, |index| MenuButton::all().get(index) == Some(self))
+    }
+
+    fn label(&self, options: &PlayOptions) -> String {
+        match self {
+            MenuButton::Play => "play".to_string(),
+            MenuButton::Continue => "continue".to_string(),
+            MenuButton::Blitz => format!("blitz {}", options.blitz.name()),
+            MenuButton::Difficulty(difficulty) => difficulty.name().to_string(),
+            MenuButton::Warnings => format!("warn {}", on_off(options.warn_inconsistent)),
+            MenuButton::Submit => format!("submit {}", on_off(options.confirm_submit)),
+            MenuButton::Layout => format!("layout {}", options.layout.name()),
+            MenuButton::Controls => "controls".to_string(),
+            MenuButton::Display => "display".to_string(),
+            MenuButton::Stats => "stats".to_string(),
+            MenuButton::Scores => "scores".to_string(),
+        }
+    }
+
+    fn material(
+        &self,
+        button_materials: &ButtonMaterials,
+        difficulty: &Difficulty,
+        focus: &MenuFocus,
+    ) -> Handle<ColorMaterial> {
+        match self {
+            _ if self.is_focused(focus) => button_materials.hovered.clone(),
+            MenuButton::Difficulty(choice) if choice == difficulty => {
+                button_materials.selected.clone()
+            }
+            _ => button_materials.normal.clone(),
+        }
+    }
+}
+
+struct DifficultySummary;
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn rules_summary(rules: &GameRules) -> String {
+    let mut summary = format!(
+        "{} pegs - {} colors - {} rows",
+        rules.code_length, rules.colors, rules.max_attempts
+    );
+    if !rules.duplicates {
+        summary.push_str(" - unique");
+    }
+    if rules.blanks {
+        summary.push_str(" - blanks");
+    }
+    summary
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn button_system(
     button_materials: Res<ButtonMaterials>,
     mut interaction_query: Query<
-        (&Interaction, &mut Handle<ColorMaterial>, &Children),
+        (
+            &Interaction,
+            &mut Handle<ColorMaterial>,
+            &MenuButton,
+            &Children,
+        ),
         (Changed<Interaction>, With<Button>),
     >,
     mut app_state: ResMut<State<AppState>>,
+    mut difficulty: ResMut<Difficulty>,
+    mut rules: ResMut<GameRules>,
+    mut options: ResMut<PlayOptions>,
+    mut page: ResMut<SettingsPage>,
+    mut resume: ResMut<Resume>,
+    focus: Res<MenuFocus>,
     mut text_query: Query<&mut Text>,
 ) {
-    for (interaction, mut material, children) in interaction_query.iter_mut() {
+    for (interaction, mut material, button, children) in interaction_query.iter_mut() {
         let mut text = text_query.get_mut(children[0]).unwrap();
         match *interaction {
             Interaction::Clicked => {
-                app_state.push(AppState::InGame).unwrap();
+                activate(
+                    *button,
+                    &mut app_state,
+                    &mut difficulty,
+                    &mut rules,
+                    &mut options,
+                    &mut page,
+                    &mut resume,
+                );
             }
             Interaction::Hovered => {
-                text.sections[0].value = "PLAY".to_string();
+                text.sections[0].value = button.label(&options).to_uppercase();
                 *material = button_materials.hovered.clone();
             }
             Interaction::None => {
-                text.sections[0].value = "play".to_string();
-                *material = button_materials.normal.clone();
+                text.sections[0].value = button.label(&options);
+                *material = button.material(&button_materials, &difficulty, &focus);
+            }
+        }
+    }
+}
+
+fn activate(
+    button: MenuButton,
+    app_state: &mut State<AppState>,
+    difficulty: &mut Difficulty,
+    rules: &mut GameRules,
+    options: &mut PlayOptions,
+    page: &mut SettingsPage,
+    resume: &mut Resume,
+) {
+    match button {
+        MenuButton::Play => {
+            app_state.push(AppState::InGame).unwrap();
+        }
+        MenuButton::Continue => {
+            resume.requested = true;
+            app_state.push(AppState::InGame).unwrap();
+        }
+        MenuButton::Blitz => {
+            options.blitz = options.blitz.next();
+        }
+        MenuButton::Difficulty(choice) => {
+            *difficulty = choice;
+            *rules = choice.rules();
+        }
+        MenuButton::Warnings => {
+            options.warn_inconsistent = !options.warn_inconsistent;
+        }
+        MenuButton::Submit => {
+            options.confirm_submit = !options.confirm_submit;
+        }
+        MenuButton::Layout => {
+            options.layout = options.layout.next();
+        }
+        MenuButton::Controls => {
+            *page = SettingsPage::Controls;
+            app_state.push(AppState::Settings).unwrap();
+        }
+        MenuButton::Display => {
+            *page = SettingsPage::Display;
+            app_state.push(AppState::Settings).unwrap();
+        }
+        MenuButton::Stats => {
+            app_state.push(AppState::Stats).unwrap();
+        }
+        MenuButton::Scores => {
+            app_state.push(AppState::Scores).unwrap();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gamepad_system(
+    mut pad_events: EventReader<PadEvent>,
+    mut focus: ResMut<MenuFocus>,
+    mut app_state: ResMut<State<AppState>>,
+    mut difficulty: ResMut<Difficulty>,
+    mut rules: ResMut<GameRules>,
+    mut options: ResMut<PlayOptions>,
+    mut page: ResMut<SettingsPage>,
+    mut resume: ResMut<Resume>,
+) {
+    for event in pad_events.iter() {
+        let buttons = MenuButton::all();
+        let index = focus.index.unwrap_or(0) % buttons.len();
+        match event {
+            PadEvent::Up | PadEvent::Left => {
+                focus.index = Some((index + buttons.len() - 1) % buttons.len());
+            }
+            PadEvent::Down | PadEvent::Right => {
+                focus.index = Some((index + 1) % buttons.len());
+            }
+            PadEvent::Accept => match focus.index {
+                Some(_) => activate(
+                    buttons[index],
+                    &mut app_state,
+                    &mut difficulty,
+                    &mut rules,
+                    &mut options,
+                    &mut page,
+                    &mut resume,
+                ),
+                None => focus.index = Some(0),
+            },
+            PadEvent::Start => {
+                activate(
+                    MenuButton::Play,
+                    &mut app_state,
+                    &mut difficulty,
+                    &mut rules,
+                    &mut options,
+                    &mut page,
+                    &mut resume,
+                );
+                return;
+            }
+            PadEvent::Cancel => {
+                focus.index = None;
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn difficulty_system(
+    button_materials: Res<ButtonMaterials>,
+    difficulty: Res<Difficulty>,
+    rules: Res<GameRules>,
+    options: Res<PlayOptions>,
+    focus: Res<MenuFocus>,
+    query: Query<(
+        &Interaction,
+        &mut Handle<ColorMaterial>,
+        &MenuButton,
+        &Children,
+    )>,
+    mut text_query: Query<&mut Text, Without<DifficultySummary>>,
+    summary_query: Query<&mut Text, With<DifficultySummary>>,
+) {
+    if difficulty.is_changed() || focus.is_changed() || options.is_changed() {
+        query.for_each_mut(|(interaction, mut material, button, children)| {
+            if *interaction == Interaction::None {
+                *material = button.material(&button_materials, &difficulty, &focus);
+                if let Ok(mut text) = text_query.get_mut(children[0]) {
+                    text.sections[0].value = if button.is_focused(&focus) {
+                        button.label(&options).to_uppercase()
+                    } else {
+                        button.label(&options)
+                    };
+                }
+            }
+        });
+        summary_query.for_each_mut(|mut text| {
+            text.sections[0].value = rules_summary(&rules);
+        });
+    }
+}
+
 fn clear(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
     if let Some(entities) = snapshots.get_mut_snap(&String::from("Menu")) {
         for entity in entities.iter() {
@@ -88,11 +357,16 @@ fn clear(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn resume(
     mut cmds: Commands,
     mut snapshots: ResMut<Snapshots>,
     button_materials: Res<ButtonMaterials>,
     assets: Res<MyAssets>,
+    difficulty: Res<Difficulty>,
+    rules: Res<GameRules>,
+    options: Res<PlayOptions>,
+    focus: Res<MenuFocus>,
 ) {
     let entities = snapshots.get_mut_snap(&String::from("Menu")).unwrap();
     entities.push(cmds.spawn_bundle(UiCameraBundle::default()).id());
@@ -126,8 +400,154 @@ fn resume(
                     ..Default::default()
                 });
             })
+            .insert(MenuButton::Play)
             .id(),
     );
+    entities.push(
+        cmds.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.),
+                    top: Val::Percent(20.),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for choice in Difficulty::ALL.iter() {
+                let button = MenuButton::Difficulty(*choice);
+                parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(68.0), Val::Px(32.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        material: button.material(&button_materials, &difficulty, &focus),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                button.label(&options),
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: 16.0,
+                                    color: Color::rgb(0.9, 0.9, 0.9),
+                                },
+                                Default::default(),
+                            ),
+                            ..Default::default()
+                        });
+                    })
+                    .insert(button);
+            }
+        })
+        .id(),
+    );
+    entities.push(
+        cmds.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(20.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.),
+                    top: Val::Percent(28.),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        rules_summary(&rules),
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: 14.0,
+                            color: Color::GOLD,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(DifficultySummary);
+        })
+        .id(),
+    );
+    let mut rows = vec![
+        (31., vec![MenuButton::Warnings, MenuButton::Submit]),
+        (24., vec![MenuButton::Layout, MenuButton::Blitz]),
+        (17., vec![MenuButton::Controls, MenuButton::Display]),
+        (10., vec![MenuButton::Stats, MenuButton::Scores]),
+    ];
+    if SavedGame::exists() {
+        rows.push((38., vec![MenuButton::Continue]));
+    }
+    for (bottom, row) in rows {
+        entities.push(
+            cmds.spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        left: Val::Px(0.),
+                        bottom: Val::Percent(bottom),
+                        ..Default::default()
+                    },
+                    justify_content: JustifyContent::SpaceEvenly,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                material: button_materials.transparent.clone(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                for button in row.iter() {
+                    parent
+                        .spawn_bundle(ButtonBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(130.0), Val::Px(32.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            material: button_materials.normal.clone(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn_bundle(TextBundle {
+                                text: Text::with_section(
+                                    button.label(&options),
+                                    TextStyle {
+                                        font: assets.font.clone(),
+                                        font_size: 16.0,
+                                        color: Color::rgb(0.9, 0.9, 0.9),
+                                    },
+                                    Default::default(),
+                                ),
+                                ..Default::default()
+                            });
+                        })
+                        .insert(*button);
+                }
+            })
+            .id(),
+        );
+    }
     entities.push(
         cmds.spawn_bundle(TextBundle {
             text: Text {
@@ -0,0 +1,283 @@
+use crate::{
+    action::Action,
+    gamepad::PadEvent,
+    resource::{
+        assets::Assets as MyAssets, button::ButtonMaterials, clock::Clock, snapshots::Snapshots,
+    },
+    settings::SettingsPage,
+    state::AppState,
+};
+use bevy::prelude::*;
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<Pause>()
+            .add_system(toggle_system.system())
+            .add_system_set(
+                SystemSet::on_enter(AppState::Paused)
+                    .with_system(setup.system())
+                    .with_system(draw_menu.system()),
+            )
+            .add_system_set(SystemSet::on_resume(AppState::Paused).with_system(draw_menu.system()))
+            .add_system_set(SystemSet::on_pause(AppState::Paused).with_system(clear_menu.system()))
+            .add_system_set(SystemSet::on_exit(AppState::Paused).with_system(clear.system()))
+            .add_system_set(
+                SystemSet::on_update(AppState::Paused)
+                    .with_system(button_system.system())
+                    .with_system(gamepad_system.system()),
+            )
+            .add_system_set(SystemSet::on_resume(AppState::InGame).with_system(resume.system()));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PauseAction {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
+
+impl PauseAction {
+    fn all() -> [PauseAction; 4] {
+        [
+            PauseAction::Resume,
+            PauseAction::Restart,
+            PauseAction::Settings,
+            PauseAction::Quit,
+        ]
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            PauseAction::Resume => "resume",
+            PauseAction::Restart => "restart",
+            PauseAction::Settings => "settings",
+            PauseAction::Quit => "quit to menu",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Pause {
+    next: Option<PauseAction>,
+    focus: usize,
+}
+
+fn toggle_system(
+    mut actions: EventReader<Action>,
+    mut pad_events: EventReader<PadEvent>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    let menu = actions.iter().any(|action| *action == Action::Menu);
+    let start = pad_events.iter().any(|event| *event == PadEvent::Start);
+    if !(menu || start) {
+        return;
+    }
+    match app_state.current() {
+        AppState::InGame => app_state.push(AppState::Paused).unwrap(),
+        AppState::Paused => app_state.pop().unwrap(),
+        _ => {}
+    }
+}
+
+fn setup(
+    mut cmds: Commands,
+    mut snapshots: ResMut<Snapshots>,
+    mut pause: ResMut<Pause>,
+    mut clock: ResMut<Clock>,
+    time: Res<Time>,
+    button_materials: Res<ButtonMaterials>,
+) {
+    pause.next = None;
+    pause.focus = 0;
+    clock.pause(time.seconds_since_startup());
+    let overlay = cmds
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            },
+            material: button_materials.overlay.clone(),
+            ..Default::default()
+        })
+        .id();
+    snapshots.snap(&String::from("Pause"), vec![overlay]);
+}
+
+fn draw_menu(
+    mut cmds: Commands,
+    mut snapshots: ResMut<Snapshots>,
+    button_materials: Res<ButtonMaterials>,
+    assets: Res<MyAssets>,
+) {
+    let style = |font_size: f32, color: Color| TextStyle {
+        font: assets.font.clone(),
+        font_size,
+        color,
+    };
+    let menu = cmds
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                style: Style {
+                    margin: Rect::all(Val::Px(12.0)),
+                    ..Default::default()
+                },
+                text: Text::with_section("paused", style(28.0, Color::GOLD), Default::default()),
+                ..Default::default()
+            });
+            for action in PauseAction::all().iter() {
+                parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(160.0), Val::Px(32.0)),
+                            margin: Rect::all(Val::Px(4.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        material: button_materials.normal.clone(),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                action.label(),
+                                style(16.0, Color::rgb(0.9, 0.9, 0.9)),
+                                Default::default(),
+                            ),
+                            ..Default::default()
+                        });
+                    })
+                    .insert(*action);
+            }
+        })
+        .id();
+    snapshots.snap(&String::from("PauseMenu"), vec![menu]);
+}
+
+fn activate(
+    action: PauseAction,
+    pause: &mut Pause,
+    app_state: &mut State<AppState>,
+    page: &mut SettingsPage,
+) {
+    match action {
+        PauseAction::Settings => {
+            *page = SettingsPage::Controls;
+            app_state.push(AppState::Settings).unwrap();
+        }
+        _ => {
+            pause.next = Some(action);
+            app_state.pop().unwrap();
+        }
+    }
+}
+
+fn button_system(
+    button_materials: Res<ButtonMaterials>,
+    mut pause: ResMut<Pause>,
+    mut page: ResMut<SettingsPage>,
+    mut app_state: ResMut<State<AppState>>,
+    mut interaction_query: Query<
+        (&Interaction, &PauseAction, &mut Handle<ColorMaterial>),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, action, mut material) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                activate(*action, &mut pause, &mut app_state, &mut page);
+                return;
+            }
+            Interaction::Hovered => *material = button_materials.hovered.clone(),
+            Interaction::None => *material = button_materials.normal.clone(),
+        }
+    }
+}
+
+fn gamepad_system(
+    button_materials: Res<ButtonMaterials>,
+    mut pad_events: EventReader<PadEvent>,
+    mut pause: ResMut<Pause>,
+    mut page: ResMut<SettingsPage>,
+    mut app_state: ResMut<State<AppState>>,
+    query: Query<(&PauseAction, &mut Handle<ColorMaterial>)>,
+) {
+    let count = PauseAction::all().len();
+    let mut moved = false;
+    for event in pad_events.iter() {
+        match event {
+            PadEvent::Up | PadEvent::Left => pause.focus = (pause.focus + count - 1) % count,
+            PadEvent::Down | PadEvent::Right => pause.focus = (pause.focus + 1) % count,
+            PadEvent::Accept => {
+                let action = PauseAction::all()[pause.focus];
+                activate(action, &mut pause, &mut app_state, &mut page);
+                return;
+            }
+            PadEvent::Cancel => {
+                app_state.pop().unwrap();
+                return;
+            }
+            PadEvent::Start => {}
+        }
+        moved = true;
+    }
+    if moved {
+        let focused = PauseAction::all()[pause.focus];
+        query.for_each_mut(|(action, mut material)| {
+            *material = if *action == focused {
+                button_materials.hovered.clone()
+            } else {
+                button_materials.normal.clone()
+            };
+        });
+    }
+}
+
+fn resume(
+    mut pause: ResMut<Pause>,
+    mut clock: ResMut<Clock>,
+    time: Res<Time>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    clock.resume(time.seconds_since_startup());
+    match pause.next.take() {
+        Some(PauseAction::Restart) => app_state.set(AppState::Restart).unwrap(),
+        Some(PauseAction::Quit) => app_state.pop().unwrap(),
+        _ => {}
+    }
+}
+
+fn clear_snap(cmds: &mut Commands, snapshots: &mut Snapshots, name: &str) {
+    if let Some(entities) = snapshots.get_mut_snap(&name.to_string()) {
+        for entity in entities.iter() {
+            cmds.entity(*entity).despawn_recursive();
+        }
+        entities.clear();
+    }
+}
+
+fn clear_menu(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
+    clear_snap(&mut cmds, &mut snapshots, "PauseMenu");
+}
+
+fn clear(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
+    clear_snap(&mut cmds, &mut snapshots, "Pause");
+    clear_snap(&mut cmds, &mut snapshots, "PauseMenu");
+}
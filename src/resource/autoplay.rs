@@ -0,0 +1,28 @@
+use crate::engine::Code;
+use bevy::core::Timer;
+use bevy::prelude::{FromWorld, World};
+use bevy::tasks::Task;
+
+pub struct AutoPlay {
+    pub enabled: bool,
+    pub timer: Timer,
+    pub guess: Option<(usize, Code)>,
+    pub pending: Option<(usize, Task<Option<Code>>)>,
+}
+
+impl AutoPlay {
+    pub fn new() -> Self {
+        AutoPlay {
+            enabled: false,
+            timer: Timer::from_seconds(0.3, true),
+            guess: None,
+            pending: None,
+        }
+    }
+}
+
+impl FromWorld for AutoPlay {
+    fn from_world(_world: &mut World) -> Self {
+        AutoPlay::new()
+    }
+}
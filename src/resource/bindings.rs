@@ -0,0 +1,102 @@
+use crate::action::{Action, Direction};
+use crate::storage;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const BINDINGS_FILE: &str = "bindings.ron";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Binding {
+    pub action: Action,
+    pub keys: Vec<KeyCode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bindings {
+    pub bindings: Vec<Binding>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        let colors = [
+            (KeyCode::Key1, KeyCode::Numpad1),
+            (KeyCode::Key2, KeyCode::Numpad2),
+            (KeyCode::Key3, KeyCode::Numpad3),
+            (KeyCode::Key4, KeyCode::Numpad4),
+            (KeyCode::Key5, KeyCode::Numpad5),
+            (KeyCode::Key6, KeyCode::Numpad6),
+            (KeyCode::Key7, KeyCode::Numpad7),
+            (KeyCode::Key8, KeyCode::Numpad8),
+            (KeyCode::Key9, KeyCode::Numpad9),
+        ];
+        let mut bindings: Vec<Binding> = colors
+            .iter()
+            .enumerate()
+            .map(|(index, (key, numpad))| Binding {
+                action: Action::PickColor(index),
+                keys: vec![*key, *numpad],
+            })
+            .collect();
+        bindings.extend(
+            vec![
+                (Action::MoveCursor(Direction::Left), vec![KeyCode::Left]),
+                (Action::MoveCursor(Direction::Right), vec![KeyCode::Right]),
+                (Action::Place, vec![KeyCode::Space, KeyCode::Return]),
+                (Action::Clear, vec![KeyCode::Back]),
+                (Action::Submit, vec![KeyCode::Return]),
+                (Action::Undo, vec![KeyCode::Z]),
+                (Action::Menu, vec![KeyCode::Escape]),
+                (Action::Hint, vec![KeyCode::H]),
+            ]
+            .into_iter()
+            .map(|(action, keys)| Binding { action, keys }),
+        );
+        Bindings { bindings }
+    }
+
+    pub fn load() -> Self {
+        let mut bindings = Bindings::new();
+        match storage::load::<Bindings>(BINDINGS_FILE) {
+            Ok(Some(saved)) => {
+                for binding in bindings.bindings.iter_mut() {
+                    if let Some(saved) = saved.bindings.iter().find(|b| b.action == binding.action)
+                    {
+                        binding.keys = saved.keys.clone();
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(err) => warn!("{}", err),
+        }
+        bindings
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = storage::save(BINDINGS_FILE, self) {
+            warn!("{}", err);
+        }
+    }
+
+    pub fn keys(&self, action: Action) -> Vec<KeyCode> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.action == action)
+            .map_or_else(Vec::new, |binding| binding.keys.clone())
+    }
+
+    pub fn rebind(&mut self, action: Action, key: KeyCode) {
+        for binding in self.bindings.iter_mut() {
+            if binding.action == action {
+                binding.keys = vec![key];
+            } else {
+                binding.keys.retain(|bound| *bound != key);
+            }
+        }
+    }
+}
+
+impl FromWorld for Bindings {
+    fn from_world(_world: &mut World) -> Self {
+        Bindings::load()
+    }
+}
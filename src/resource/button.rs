@@ -4,6 +4,9 @@ pub struct ButtonMaterials {
     pub normal: Handle<ColorMaterial>,
     pub hovered: Handle<ColorMaterial>,
     pub alerte: Handle<ColorMaterial>,
+    pub selected: Handle<ColorMaterial>,
+    pub transparent: Handle<ColorMaterial>,
+    pub overlay: Handle<ColorMaterial>,
     //pressed: Handle<ColorMaterial>,
 }
 
@@ -14,6 +17,9 @@ impl FromWorld for ButtonMaterials {
             normal: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
             hovered: materials.add(Color::rgb(0.25, 0.25, 0.25).into()),
             alerte: materials.add(Color::hex("750800").unwrap().into()),
+            selected: materials.add(Color::rgb(0.2, 0.35, 0.55).into()),
+            transparent: materials.add(Color::NONE.into()),
+            overlay: materials.add(Color::rgba(0., 0., 0., 0.8).into()),
             //pressed: materials.add(Color::rgb(0.35, 0.75, 0.35).into()),
         }
     }
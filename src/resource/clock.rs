@@ -0,0 +1,101 @@
+use crate::resource::options::Blitz;
+use bevy::prelude::{FromWorld, World};
+
+pub struct Clock {
+    pub start: Option<f64>,
+    pub row_start: Option<f64>,
+    pub row: usize,
+    pub end: Option<f64>,
+    pub paused: Option<f64>,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Clock {
+            start: None,
+            row_start: None,
+            row: 0,
+            end: None,
+            paused: None,
+        }
+    }
+
+    pub fn resumed(row: usize, elapsed: f64, row_elapsed: f64, now: f64) -> Self {
+        Clock {
+            start: Some(now - elapsed),
+            row_start: Some(now - row_elapsed),
+            row,
+            ..Clock::new()
+        }
+    }
+
+    pub fn start(&mut self, row: usize, now: f64) {
+        if self.start.is_none() {
+            self.start = Some(now);
+            self.row_start = Some(now);
+            self.row = row;
+        }
+    }
+
+    pub fn next_row(&mut self, row: usize, now: f64) {
+        if self.start.is_some() && self.row != row {
+            self.row_start = Some(now);
+            self.row = row;
+        }
+    }
+
+    pub fn stop(&mut self, now: f64) {
+        if self.end.is_none() {
+            self.end = Some(now);
+        }
+    }
+
+    pub fn pause(&mut self, now: f64) {
+        if self.paused.is_none() {
+            self.paused = Some(now);
+        }
+    }
+
+    pub fn resume(&mut self, now: f64) {
+        if let Some(paused) = self.paused.take() {
+            if self.end.is_none() {
+                self.start = self.start.map(|start| start + now - paused);
+                self.row_start = self.row_start.map(|start| start + now - paused);
+            }
+        }
+    }
+
+    fn since(&self, start: Option<f64>, now: f64) -> f64 {
+        match start {
+            Some(start) => self.end.or(self.paused).unwrap_or(now) - start,
+            None => 0.,
+        }
+    }
+
+    pub fn elapsed(&self, now: f64) -> f64 {
+        self.since(self.start, now)
+    }
+
+    pub fn row_elapsed(&self, now: f64) -> f64 {
+        self.since(self.row_start, now)
+    }
+
+    pub fn remaining(&self, blitz: Blitz, now: f64) -> Option<f64> {
+        match blitz {
+            Blitz::Off => None,
+            Blitz::Total(limit) => Some(limit as f64 - self.since(self.start, now)),
+            Blitz::PerRow(limit) => Some(limit as f64 - self.since(self.row_start, now)),
+        }
+    }
+}
+
+impl FromWorld for Clock {
+    fn from_world(_world: &mut World) -> Self {
+        Clock::new()
+    }
+}
+
+pub fn format_time(seconds: f64) -> String {
+    let seconds = seconds.max(0.) as u64;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
@@ -3,12 +3,18 @@ use bevy::prelude::{Color, FromWorld, World};
 pub struct MastermindColors {
     pub clear_color: Color,
     pub case_colors: (Color, Color),
-    pub pieces_colors: [(Color, Color, Color); 6],
+    pub pieces_colors: Vec<(Color, Color, Color)>,
     pub pieces_case_colors: (Color, Color),
     pub result_bad_colors: (Color, Color),
     pub result_good_colors: (Color, Color),
     pub result_case_colors: (Color, Color),
     pub secret_case_hidden_colors: (Color, Color),
+    pub alert_colors: (Color, Color),
+    pub remaining_color: Color,
+    pub warning_colors: (Color, Color),
+    pub submit_colors: (Color, Color),
+    pub focus_colors: (Color, Color),
+    pub bar_colors: (Color, Color),
 }
 
 impl FromWorld for MastermindColors {
@@ -17,7 +23,7 @@ impl FromWorld for MastermindColors {
             clear_color: Color::BLACK,
             case_colors: (Color::WHITE, Color::WHITE),
             pieces_case_colors: (Color::BLACK, Color::BLACK),
-            pieces_colors: [
+            pieces_colors: vec![
                 (
                     Color::hex("36342F").unwrap(),
                     Color::hex("878377").unwrap(),
@@ -48,11 +54,32 @@ impl FromWorld for MastermindColors {
                     Color::hex("3CB510").unwrap(),
                     Color::WHITE,
                 ), //GREEN
+                (
+                    Color::hex("F58A0A").unwrap(),
+                    Color::hex("B56507").unwrap(),
+                    Color::WHITE,
+                ), //ORANGE
+                (
+                    Color::hex("9B2DDB").unwrap(),
+                    Color::hex("6B1F99").unwrap(),
+                    Color::WHITE,
+                ), //PURPLE
+                (
+                    Color::hex("D9D9D9").unwrap(),
+                    Color::hex("A6A6A6").unwrap(),
+                    Color::BLACK,
+                ), //BLANK
             ],
             result_bad_colors: (Color::BLACK, Color::GRAY),
             result_good_colors: (Color::WHITE, Color::BLACK),
             result_case_colors: (Color::GRAY, Color::BLACK),
             secret_case_hidden_colors: (Color::BLACK, Color::WHITE),
+            alert_colors: (Color::NONE, Color::hex("F51000").unwrap()),
+            remaining_color: Color::GOLD,
+            warning_colors: (Color::NONE, Color::hex("750800").unwrap()),
+            submit_colors: (Color::hex("51F516").unwrap(), Color::hex("3CB510").unwrap()),
+            focus_colors: (Color::NONE, Color::hex("F58A0A").unwrap()),
+            bar_colors: (Color::hex("1D7CF2").unwrap(), Color::hex("1A55A1").unwrap()),
         }
     }
 }
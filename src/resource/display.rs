@@ -0,0 +1,70 @@
+use bevy::window::WindowMode;
+use serde::{Deserialize, Serialize};
+
+pub const MSAA_LEVELS: [u32; 3] = [1, 4, 8];
+pub const UI_SCALES: [f32; 4] = [0.75, 1., 1.25, 1.5];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "windowed",
+            DisplayMode::Borderless => "borderless",
+            DisplayMode::Fullscreen => "fullscreen",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen { use_size: false },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    pub mode: DisplayMode,
+    pub vsync: bool,
+    pub msaa: u32,
+    pub ui_scale: f32,
+}
+
+impl DisplaySettings {
+    pub fn new() -> Self {
+        DisplaySettings {
+            mode: DisplayMode::Windowed,
+            vsync: true,
+            msaa: 8,
+            ui_scale: 1.,
+        }
+    }
+
+    pub fn next_msaa(&self) -> u32 {
+        next_in(&MSAA_LEVELS, self.msaa)
+    }
+
+    pub fn next_ui_scale(&self) -> f32 {
+        next_in(&UI_SCALES, self.ui_scale)
+    }
+}
+
+fn next_in<T: Copy + PartialEq>(values: &[T], current: T) -> T {
+    let index = values.iter().position(|value| *value == current);
+    values[index.map_or(0, |index| (index + 1) % values.len())]
+}
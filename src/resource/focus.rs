@@ -0,0 +1,39 @@
+use bevy::prelude::{FromWorld, World};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusArea {
+    Buttons,
+    Slots,
+    Palette,
+}
+
+pub struct Focus {
+    pub area: FocusArea,
+    pub slot: usize,
+    pub palette: usize,
+    pub button: usize,
+    pub visible: bool,
+}
+
+impl Focus {
+    pub fn new() -> Self {
+        Focus {
+            area: FocusArea::Slots,
+            slot: 0,
+            palette: 0,
+            button: 0,
+            visible: false,
+        }
+    }
+}
+
+impl FromWorld for Focus {
+    fn from_world(_world: &mut World) -> Self {
+        Focus::new()
+    }
+}
+
+#[derive(Default)]
+pub struct MenuFocus {
+    pub index: Option<usize>,
+}
@@ -0,0 +1,87 @@
+use crate::resource::rules::Difficulty;
+use crate::resource::score::Score;
+use crate::storage;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const HIGHSCORES_FILE: &str = "highscores.ron";
+pub const TABLE_SIZE: usize = 5;
+pub const NAME_LENGTH: usize = 12;
+const DEFAULT_NAME: &str = "player";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub name: String,
+    pub points: u32,
+    pub rows: usize,
+    pub seconds: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScores {
+    pub tables: HashMap<Difficulty, Vec<HighScore>>,
+}
+
+impl HighScores {
+    pub fn load() -> Self {
+        storage::load::<HighScores>(HIGHSCORES_FILE)
+            .unwrap_or_else(|err| {
+                warn!("{}", err);
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = storage::save(HIGHSCORES_FILE, self) {
+            warn!("{}", err);
+        }
+    }
+
+    pub fn table(&self, difficulty: Difficulty) -> &[HighScore] {
+        self.tables.get(&difficulty).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_record(&self, difficulty: Difficulty, points: u32) -> bool {
+        let table = self.table(difficulty);
+        points > 0 && (table.len() < TABLE_SIZE || table.iter().any(|entry| entry.points < points))
+    }
+
+    pub fn insert(&mut self, difficulty: Difficulty, name: &str, score: &Score) {
+        let name = name.trim();
+        let table = self.tables.entry(difficulty).or_insert_with(Vec::new);
+        table.push(HighScore {
+            name: if name.is_empty() {
+                DEFAULT_NAME.to_string()
+            } else {
+                name.to_string()
+            },
+            points: score.points,
+            rows: score.rows,
+            seconds: score.seconds,
+        });
+        table.sort_by(|a, b| b.points.cmp(&a.points));
+        table.truncate(TABLE_SIZE);
+    }
+}
+
+impl FromWorld for HighScores {
+    fn from_world(_world: &mut World) -> Self {
+        HighScores::load()
+    }
+}
+
+pub struct NameEntry {
+    pub difficulty: Difficulty,
+    pub score: Score,
+    pub name: String,
+}
+
+impl NameEntry {
+    pub fn commit(&self, highscores: &mut HighScores) {
+        highscores.insert(self.difficulty, &self.name, &self.score);
+        highscores.save();
+    }
+}
@@ -1,41 +1,39 @@
+use crate::engine::{Code, Color, Game};
+use crate::resource::rules::GameRules;
 use bevy::prelude::{FromWorld, World};
-use mastermind::Game;
 
 pub struct Mastermind {
     pub state: Game,
 }
 
 impl Mastermind {
-    pub fn new() -> Self {
-        Mastermind { state: Game::new() }
+    pub fn new(rules: &GameRules) -> Self {
+        Mastermind {
+            state: Game::new(rules),
+        }
     }
 }
 
 impl FromWorld for Mastermind {
-    fn from_world(_world: &mut World) -> Self {
-        Mastermind { state: Game::new() }
+    fn from_world(world: &mut World) -> Self {
+        let rules = world.get_resource::<GameRules>().unwrap();
+        Mastermind::new(rules)
     }
 }
 
-pub fn is_all_some(tab: &[Option<mastermind::Color>; 4]) -> bool {
-    let mut bool = true;
-    for code in tab.iter() {
-        bool &= code.is_some();
-    }
-    bool
+pub fn is_all_some(tab: &[Option<Color>]) -> bool {
+    tab.iter().all(|code| code.is_some())
 }
 
-pub fn some_code_to_code(tab: &[Option<mastermind::Color>; 4]) -> mastermind::Code {
-    let mut code: [mastermind::Color; 4] = [
-        mastermind::Color::Black,
-        mastermind::Color::Black,
-        mastermind::Color::Black,
-        mastermind::Color::Black,
-    ];
-    for i in 0..4 {
-        if let Some(color) = &tab[i] {
-            code[i] = color.clone();
-        }
-    }
-    code as mastermind::Code
+pub fn some_code_to_code(tab: &[Option<Color>]) -> Code {
+    tab.iter()
+        .map(|color| color.unwrap_or(Color::from_value(0)))
+        .collect()
+}
+
+pub fn find_duplicate(tab: &[Option<Color>], col: usize, color: Color) -> Option<usize> {
+    tab.iter()
+        .enumerate()
+        .find(|(i, code)| *i != col && **code == Some(color))
+        .map(|(i, _)| i)
 }
@@ -1,7 +1,19 @@
+pub mod autoplay;
+pub mod bindings;
 pub mod button;
+pub mod clock;
 pub mod color;
+pub mod display;
+pub mod focus;
+pub mod highscores;
 pub mod mastermind;
+pub mod options;
+pub mod rules;
+pub mod savegame;
+pub mod score;
+pub mod settings;
 pub mod snapshots;
+pub mod stats;
 pub mod state;
 pub mod structure;
 pub mod assets;
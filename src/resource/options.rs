@@ -0,0 +1,99 @@
+use bevy::prelude::{FromWorld, World};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Layout {
+    Auto,
+    Portrait,
+    Landscape,
+}
+
+impl Layout {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Auto => "auto",
+            Layout::Portrait => "portrait",
+            Layout::Landscape => "landscape",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Layout::Auto => Layout::Portrait,
+            Layout::Portrait => Layout::Landscape,
+            Layout::Landscape => Layout::Auto,
+        }
+    }
+
+    pub fn is_landscape(&self, size: (f32, f32)) -> bool {
+        match self {
+            Layout::Auto => size.0 > size.1,
+            Layout::Portrait => false,
+            Layout::Landscape => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Blitz {
+    Off,
+    Total(u32),
+    PerRow(u32),
+}
+
+pub const BLITZ_PRESETS: [Blitz; 5] = [
+    Blitz::Off,
+    Blitz::Total(300),
+    Blitz::Total(120),
+    Blitz::PerRow(60),
+    Blitz::PerRow(30),
+];
+
+impl Blitz {
+    pub fn name(&self) -> String {
+        match self {
+            Blitz::Off => "off".to_string(),
+            Blitz::Total(seconds) => format!("{}:{:02}", seconds / 60, seconds % 60),
+            Blitz::PerRow(seconds) => format!("{}s/row", seconds),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = BLITZ_PRESETS
+            .iter()
+            .position(|preset| preset == self)
+            .map_or(0, |index| (index + 1) % BLITZ_PRESETS.len());
+        BLITZ_PRESETS[index]
+    }
+
+    pub fn limit(&self) -> Option<u32> {
+        match self {
+            Blitz::Off => None,
+            Blitz::Total(seconds) | Blitz::PerRow(seconds) => Some(*seconds),
+        }
+    }
+}
+
+pub struct PlayOptions {
+    pub warn_inconsistent: bool,
+    pub confirm_submit: bool,
+    pub layout: Layout,
+    pub blitz: Blitz,
+}
+
+impl PlayOptions {
+    pub fn new() -> Self {
+        PlayOptions {
+            warn_inconsistent: false,
+            confirm_submit: false,
+            layout: Layout::Auto,
+            blitz: Blitz::Off,
+        }
+    }
+}
+
+impl FromWorld for PlayOptions {
+    fn from_world(_world: &mut World) -> Self {
+        PlayOptions::new()
+    }
+}
@@ -0,0 +1,117 @@
+use crate::engine::Color;
+use bevy::prelude::{FromWorld, World};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_COLORS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRules {
+    pub code_length: usize,
+    pub colors: usize,
+    pub max_attempts: usize,
+    pub duplicates: bool,
+    pub blanks: bool,
+}
+
+impl GameRules {
+    pub fn new(code_length: usize, colors: usize, max_attempts: usize) -> Self {
+        GameRules {
+            code_length: code_length.max(1),
+            colors: colors.max(2).min(MAX_COLORS),
+            max_attempts: max_attempts.max(1),
+            duplicates: true,
+            blanks: false,
+        }
+    }
+
+    pub fn classic() -> Self {
+        GameRules::new(4, 6, 10)
+    }
+
+    pub fn with_duplicates(mut self, duplicates: bool) -> Self {
+        self.duplicates = duplicates;
+        self.clamp_code_length()
+    }
+
+    pub fn with_blanks(mut self, blanks: bool) -> Self {
+        self.blanks = blanks;
+        self.clamp_code_length()
+    }
+
+    fn clamp_code_length(mut self) -> Self {
+        if !self.duplicates {
+            self.code_length = self.code_length.min(self.palette_size());
+        }
+        self
+    }
+
+    pub fn palette_size(&self) -> usize {
+        self.colors + self.blanks as usize
+    }
+
+    pub fn palette(&self) -> Vec<Color> {
+        let mut palette: Vec<Color> = (0..self.colors).map(Color::from_value).collect();
+        if self.blanks {
+            palette.push(Color::blank());
+        }
+        palette
+    }
+
+    pub fn is_valid(&self, code: &[Color]) -> bool {
+        let palette = self.palette();
+        code.len() == self.code_length
+            && code.iter().all(|color| palette.contains(color))
+            && (self.duplicates
+                || code
+                    .iter()
+                    .enumerate()
+                    .all(|(i, color)| !code[..i].contains(color)))
+    }
+}
+
+impl FromWorld for GameRules {
+    fn from_world(_world: &mut World) -> Self {
+        GameRules::classic()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Classic,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Classic,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Classic => "classic",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        }
+    }
+
+    pub fn rules(&self) -> GameRules {
+        match self {
+            Difficulty::Easy => GameRules::new(3, 6, 10).with_duplicates(false),
+            Difficulty::Classic => GameRules::classic(),
+            Difficulty::Hard => GameRules::new(5, 8, 12),
+            Difficulty::Expert => GameRules::new(5, 8, 10).with_blanks(true),
+        }
+    }
+}
+
+impl FromWorld for Difficulty {
+    fn from_world(_world: &mut World) -> Self {
+        Difficulty::Classic
+    }
+}
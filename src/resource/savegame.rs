@@ -0,0 +1,136 @@
+use crate::engine::{self, Color, Game};
+use crate::resource::clock::Clock;
+use crate::resource::rules::Difficulty;
+use crate::resource::state::State as MState;
+use crate::storage;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const SAVEGAME_FILE: &str = "savegame.ron";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub game: Game,
+    pub row: usize,
+    pub code: Vec<Option<Color>>,
+    pub hints: usize,
+    #[serde(default)]
+    pub autoplayed: bool,
+    #[serde(default)]
+    pub elapsed: f64,
+    #[serde(default)]
+    pub row_elapsed: f64,
+}
+
+impl SavedGame {
+    pub fn new(game: &Game, state: &MState, clock: &Clock, now: f64) -> Self {
+        SavedGame {
+            game: game.clone(),
+            row: state.row,
+            code: state.code.clone(),
+            hints: state.hints,
+            autoplayed: state.autoplayed,
+            elapsed: clock.elapsed(now),
+            row_elapsed: clock.row_elapsed(now),
+        }
+    }
+
+    pub fn store(game: &Game, state: &MState, clock: &Clock, now: f64) {
+        let started = state.row > 0 || state.code.iter().any(Option::is_some);
+        match game.state() {
+            engine::State::Playable(_) if started => SavedGame::new(game, state, clock, now).save(),
+            _ => SavedGame::delete(),
+        }
+    }
+
+    pub fn exists() -> bool {
+        storage::exists(SAVEGAME_FILE)
+    }
+
+    pub fn load() -> Option<Self> {
+        let result = storage::load::<SavedGame>(SAVEGAME_FILE).and_then(|saved| match saved {
+            Some(saved) => saved
+                .validate()
+                .map(|_| Some(saved))
+                .map_err(|err| format!("invalid {}: {}", SAVEGAME_FILE, err)),
+            None => Ok(None),
+        });
+        result.unwrap_or_else(|err| {
+            warn!("{}, discarding it", err);
+            SavedGame::delete();
+            None
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let rules = self.game.rules();
+        if !Difficulty::ALL
+            .iter()
+            .any(|preset| preset.rules() == *rules)
+        {
+            return Err("rules match no difficulty preset".to_string());
+        }
+        self.game.validate()?;
+        let tries = match self.game.state() {
+            engine::State::Playable(playable) => playable.tries,
+            engine::State::Finish(_) => return Err("game is already finished".to_string()),
+        };
+        if self.row != tries.len() {
+            return Err(format!(
+                "row {} does not match {} tries",
+                self.row,
+                tries.len()
+            ));
+        }
+        if self.code.len() != rules.code_length {
+            return Err(format!(
+                "current row has {} slots instead of {}",
+                self.code.len(),
+                rules.code_length
+            ));
+        }
+        if !(self.elapsed.is_finite()
+            && self.row_elapsed.is_finite()
+            && self.elapsed >= 0.
+            && (0. ..=self.elapsed).contains(&self.row_elapsed))
+        {
+            return Err("clock times are invalid".to_string());
+        }
+        let palette = rules.palette();
+        if self
+            .code
+            .iter()
+            .flatten()
+            .any(|color| !palette.contains(color))
+        {
+            return Err("current row holds an unknown color".to_string());
+        }
+        let placed: Vec<&Color> = self.code.iter().flatten().collect();
+        if !rules.duplicates
+            && placed
+                .iter()
+                .enumerate()
+                .any(|(i, color)| placed[..i].contains(color))
+        {
+            return Err("current row repeats a color".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = storage::save(SAVEGAME_FILE, self) {
+            warn!("{}", err);
+        }
+    }
+
+    pub fn delete() {
+        if let Err(err) = storage::remove(SAVEGAME_FILE) {
+            warn!("{}", err);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Resume {
+    pub requested: bool,
+}
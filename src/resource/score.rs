@@ -0,0 +1,78 @@
+const ROW_POINTS: u32 = 100;
+const TIME_BONUS_SECONDS: f64 = 300.;
+const HINT_PENALTY: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub points: u32,
+    pub rows: usize,
+    pub seconds: f64,
+}
+
+impl Score {
+    pub fn compute(
+        win: bool,
+        rows: usize,
+        max_rows: usize,
+        seconds: f64,
+        hints: usize,
+        autoplayed: bool,
+    ) -> Self {
+        let points = if win && !autoplayed {
+            let base = (max_rows.saturating_sub(rows) as u32 + 1) * ROW_POINTS;
+            let bonus = (TIME_BONUS_SECONDS - seconds).max(0.) as u32;
+            (base + bonus).saturating_sub(hints as u32 * HINT_PENALTY)
+        } else {
+            0
+        };
+        Score {
+            points,
+            rows,
+            seconds,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SessionScores {
+    pub scores: Vec<Score>,
+}
+
+impl SessionScores {
+    pub fn push(&mut self, score: Score) {
+        self.scores.push(score);
+    }
+
+    pub fn best(&self) -> Option<u32> {
+        self.scores.iter().map(|score| score.points).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn win_scores_rows_left_and_time_bonus() {
+        assert_eq!(
+            Score::compute(true, 4, 10, 100., 0, false).points,
+            700 + 200
+        );
+        assert_eq!(Score::compute(true, 10, 10, 400., 0, false).points, 100);
+    }
+
+    #[test]
+    fn hints_cost_points() {
+        assert_eq!(
+            Score::compute(true, 4, 10, 100., 2, false).points,
+            900 - 100
+        );
+        assert_eq!(Score::compute(true, 10, 10, 400., 5, false).points, 0);
+    }
+
+    #[test]
+    fn losses_and_auto_play_score_nothing() {
+        assert_eq!(Score::compute(false, 10, 10, 30., 0, false).points, 0);
+        assert_eq!(Score::compute(true, 3, 10, 5., 0, true).points, 0);
+    }
+}
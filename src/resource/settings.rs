@@ -0,0 +1,102 @@
+use crate::resource::display::{DisplaySettings, MSAA_LEVELS};
+use crate::resource::options::{Blitz, Layout, PlayOptions};
+use crate::resource::rules::Difficulty;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+
+const SETTINGS_FILE: &str = "settings.ron";
+const MIN_WINDOW_SIZE: f32 = 200.;
+const UI_SCALE_RANGE: (f32, f32) = (0.5, 2.);
+
+pub struct SettingsError(pub Option<String>);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub difficulty: Difficulty,
+    pub warn_inconsistent: bool,
+    pub confirm_submit: bool,
+    pub layout: Layout,
+    pub blitz: Blitz,
+    pub display: DisplaySettings,
+    pub window_width: f32,
+    pub window_height: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let options = PlayOptions::new();
+        Settings {
+            difficulty: Difficulty::Classic,
+            warn_inconsistent: options.warn_inconsistent,
+            confirm_submit: options.confirm_submit,
+            layout: options.layout,
+            blitz: options.blitz,
+            display: DisplaySettings::new(),
+            window_width: 300.,
+            window_height: 600.,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, String> {
+        match storage::load::<Settings>(SETTINGS_FILE)? {
+            Some(settings) => settings
+                .validate()
+                .map(|_| settings)
+                .map_err(|errors| format!("invalid {}: {}", SETTINGS_FILE, errors.join("; "))),
+            None => Ok(Settings::default()),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(SETTINGS_FILE, self)
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        if !MSAA_LEVELS.contains(&self.display.msaa) {
+            errors.push(format!(
+                "display.msaa must be one of {:?}, found {}",
+                MSAA_LEVELS, self.display.msaa
+            ));
+        }
+        if !(UI_SCALE_RANGE.0..=UI_SCALE_RANGE.1).contains(&self.display.ui_scale) {
+            errors.push(format!(
+                "display.ui_scale must be between {} and {}, found {}",
+                UI_SCALE_RANGE.0, UI_SCALE_RANGE.1, self.display.ui_scale
+            ));
+        }
+        if self.blitz.limit() == Some(0) {
+            errors.push("blitz time limit must be positive".to_string());
+        }
+        for (name, value) in [
+            ("window_width", self.window_width),
+            ("window_height", self.window_height),
+        ]
+        .iter()
+        {
+            if !(value.is_finite() && *value >= MIN_WINDOW_SIZE) {
+                errors.push(format!(
+                    "{} must be at least {}, found {}",
+                    name, MIN_WINDOW_SIZE, value
+                ));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn options(&self) -> PlayOptions {
+        PlayOptions {
+            warn_inconsistent: self.warn_inconsistent,
+            confirm_submit: self.confirm_submit,
+            layout: self.layout,
+            blitz: self.blitz,
+        }
+    }
+}
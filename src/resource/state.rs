@@ -1,20 +1,48 @@
+use crate::engine::{Code, Color};
+use crate::resource::rules::GameRules;
 use bevy::prelude::{FromWorld, World};
 pub struct State {
     pub row: usize,
-    pub code: [Option<mastermind::Color>; 4],
+    pub code: Vec<Option<Color>>,
+    pub hints: usize,
+    pub autoplayed: bool,
+    pub confirmed: bool,
+    pub submitted: bool,
+    pub warned: Option<Code>,
+    pub history: Vec<(usize, Option<Color>)>,
 }
 
 impl State {
-    pub fn new() -> Self {
+    pub fn new(rules: &GameRules) -> Self {
         State {
             row: 0,
-            code: [Option::None; 4],
+            code: vec![Option::None; rules.code_length],
+            hints: 0,
+            autoplayed: false,
+            confirmed: false,
+            submitted: false,
+            warned: None,
+            history: Vec::new(),
         }
     }
+
+    pub fn clear_code(&mut self) {
+        for code in self.code.iter_mut() {
+            *code = Option::None;
+        }
+        self.confirmed = false;
+        self.submitted = false;
+        self.history.clear();
+    }
+
+    pub fn is_ready(&self, confirm_submit: bool) -> bool {
+        !confirm_submit || self.submitted
+    }
 }
 
 impl FromWorld for State {
-    fn from_world(_world: &mut World) -> Self {
-        State::new()
+    fn from_world(world: &mut World) -> Self {
+        let rules = world.get_resource::<GameRules>().unwrap();
+        State::new(rules)
     }
 }
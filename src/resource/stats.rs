@@ -0,0 +1,63 @@
+use crate::storage;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const STATS_FILE: &str = "stats.ron";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub played: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub distribution: Vec<usize>,
+    pub current_streak: usize,
+    pub best_streak: usize,
+}
+
+impl Stats {
+    pub fn load() -> Self {
+        storage::load::<Stats>(STATS_FILE)
+            .unwrap_or_else(|err| {
+                warn!("{}", err);
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = storage::save(STATS_FILE, self) {
+            warn!("{}", err);
+        }
+    }
+
+    pub fn record(&mut self, win: bool, rows: usize) {
+        self.played += 1;
+        if win {
+            self.wins += 1;
+            self.current_streak += 1;
+            self.best_streak = self.best_streak.max(self.current_streak);
+            if self.distribution.len() < rows {
+                self.distribution.resize(rows, 0);
+            }
+            self.distribution[rows - 1] += 1;
+        } else {
+            self.losses += 1;
+            self.current_streak = 0;
+        }
+    }
+
+    pub fn win_rate(&self) -> usize {
+        if self.played == 0 {
+            0
+        } else {
+            self.wins * 100 / self.played
+        }
+    }
+}
+
+impl FromWorld for Stats {
+    fn from_world(_world: &mut World) -> Self {
+        Stats::load()
+    }
+}
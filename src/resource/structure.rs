@@ -1,35 +1,157 @@
+use crate::resource::options::PlayOptions;
+use crate::resource::rules::GameRules;
 use bevy::{
-    math::Vec3,
+    math::{Vec2, Vec3},
     prelude::{FromWorld, World},
     window::WindowDescriptor,
 };
 
+pub const HISTORY_SCALE: f32 = 0.5;
+
 pub struct Structure {
+    pub origin: Vec3,
+    pub landscape: bool,
     pub piece_size: f32,
+    pub hud_height: f32,
     pub pieces_position: Vec3,
+    pub pieces_step: Vec3,
     pub boardgame_position: Vec3,
     pub secrets_position: Vec3,
+    pub history_position: Option<Vec3>,
     pub animation_start: Vec3,
     pub animation_end: Vec3,
 }
 
-impl FromWorld for Structure {
-    fn from_world(world: &mut World) -> Self {
-        let window = world.get_resource::<WindowDescriptor>().unwrap();
-        let size = (window.width, window.height);
-        let piece_size = (size.0 / 6.).min(size.1 / 12.);
+impl Structure {
+    pub fn new(size: (f32, f32), rules: &GameRules, landscape: bool) -> Self {
+        if landscape {
+            Structure::landscape(size, rules)
+        } else {
+            Structure::portrait(size, rules)
+        }
+    }
+
+    fn portrait(size: (f32, f32), rules: &GameRules) -> Self {
+        let columns = (rules.palette_size() as f32).max(rules.code_length as f32 + 3.);
+        let rows = rules.max_attempts as f32 + 3.;
+        let piece_size = (size.0 / columns).min(size.1 / rows);
         let initial_positon: Vec3 = Vec3::new(
-            (size.0 - (piece_size * 6.)) / 2.,
-            (size.1 - (piece_size * 12.)) / 2.,
+            (size.0 - (piece_size * columns)) / 2.,
+            (size.1 - (piece_size * rows)) / 2.,
             0.,
         );
+        let boardgame_position = initial_positon
+            + Vec3::new(
+                (columns - (rules.code_length as f32 + 2.)) * piece_size / 2.,
+                piece_size,
+                0.,
+            );
         Structure {
-            piece_size: piece_size,
-            pieces_position: initial_positon,
-            boardgame_position: initial_positon + Vec3::new(piece_size / 2., piece_size, 0.),
-            secrets_position: initial_positon + Vec3::new(piece_size / 2., piece_size * 10., 0.),
+            origin: initial_positon,
+            landscape: false,
+            piece_size,
+            hud_height: piece_size,
+            pieces_position: initial_positon
+                + Vec3::new(
+                    (columns - rules.palette_size() as f32) * piece_size / 2.,
+                    0.,
+                    0.,
+                ),
+            pieces_step: Vec3::new(piece_size, 0., 0.),
+            boardgame_position,
+            secrets_position: boardgame_position
+                + Vec3::new(0., piece_size * rules.max_attempts as f32, 0.),
+            history_position: None,
             animation_start: initial_positon + Vec3::new(0., -piece_size, 0.),
-            animation_end: initial_positon + Vec3::new(0., piece_size * 12., 0.),
+            animation_end: initial_positon + Vec3::new(0., piece_size * rows, 0.),
         }
     }
+
+    fn landscape(size: (f32, f32), rules: &GameRules) -> Self {
+        let history_columns = rules.code_length as f32 * HISTORY_SCALE + 3.;
+        let columns = 1.5 + (rules.code_length as f32 + 2.) + 0.5 + history_columns;
+        let rows = (rules.max_attempts as f32 + 2.).max(rules.palette_size() as f32 + 1.);
+        let piece_size = (size.0 / columns).min(size.1 / rows);
+        let initial_positon: Vec3 = Vec3::new(
+            (size.0 - (piece_size * columns)) / 2.,
+            (size.1 - (piece_size * rows)) / 2.,
+            0.,
+        );
+        let board_rows = rules.max_attempts as f32 + 1.;
+        let boardgame_position = initial_positon
+            + Vec3::new(1.5 * piece_size, (rows - 1. - board_rows) * piece_size, 0.);
+        let secrets_position =
+            boardgame_position + Vec3::new(0., piece_size * rules.max_attempts as f32, 0.);
+        Structure {
+            origin: initial_positon,
+            landscape: true,
+            piece_size,
+            hud_height: piece_size,
+            pieces_position: initial_positon
+                + Vec3::new(
+                    0.,
+                    (rows - 1. - rules.palette_size() as f32) * piece_size / 2.,
+                    0.,
+                ),
+            pieces_step: Vec3::new(0., piece_size, 0.),
+            boardgame_position,
+            secrets_position,
+            history_position: Some(
+                secrets_position + Vec3::new((rules.code_length as f32 + 2.5) * piece_size, 0., 0.),
+            ),
+            animation_start: initial_positon + Vec3::new(0., -piece_size, 0.),
+            animation_end: initial_positon + Vec3::new(0., piece_size * rows, 0.),
+        }
+    }
+
+    pub fn palette_position(&self, index: usize) -> Vec3 {
+        self.pieces_position + self.pieces_step * index as f32
+    }
+
+    pub fn history_entry(&self, index: usize) -> Option<Vec3> {
+        self.history_position.map(|position| {
+            position
+                - Vec3::new(
+                    0.,
+                    (index + 1) as f32 * self.piece_size * HISTORY_SCALE * 1.5,
+                    0.,
+                )
+        })
+    }
+}
+
+impl Structure {
+    pub fn screen_position(&self, translation: Vec3) -> Vec2 {
+        Vec2::new(
+            translation.x + self.piece_size / 2.,
+            translation.y + self.piece_size / 2.,
+        )
+    }
+
+    pub fn relayout(&self, translation: Vec3, layout: &Structure) -> Vec3 {
+        let ratio = layout.piece_size / self.piece_size;
+        let position = layout.origin + (translation - self.origin) * ratio;
+        Vec3::new(position.x, position.y, translation.z)
+    }
+
+    pub fn world_position(&self, position: Vec2) -> Vec3 {
+        Vec3::new(
+            position.x - self.piece_size / 2.,
+            position.y - self.piece_size / 2.,
+            0.,
+        )
+    }
+}
+
+impl FromWorld for Structure {
+    fn from_world(world: &mut World) -> Self {
+        let window = world.get_resource::<WindowDescriptor>().unwrap();
+        let rules = world.get_resource::<GameRules>().unwrap();
+        let options = world.get_resource::<PlayOptions>().unwrap();
+        Structure::new(
+            (window.width, window.height),
+            rules,
+            options.layout.is_landscape((window.width, window.height)),
+        )
+    }
 }
@@ -0,0 +1,121 @@
+use crate::{
+    action::{Action, Direction},
+    gamepad::PadEvent,
+    resource::{
+        assets::Assets as MyAssets, button::ButtonMaterials, clock::format_time,
+        highscores::HighScores, rules::Difficulty, snapshots::Snapshots,
+    },
+    screen::{back_button_system, spawn_screen, ScreenText},
+    state::AppState,
+};
+use bevy::prelude::*;
+
+pub struct ScoresPlugin;
+
+impl Plugin for ScoresPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<HighScores>()
+            .insert_resource(ScoresPage(Difficulty::Classic))
+            .add_system_set(SystemSet::on_enter(AppState::Scores).with_system(setup.system()))
+            .add_system_set(SystemSet::on_exit(AppState::Scores).with_system(clear.system()))
+            .add_system_set(
+                SystemSet::on_update(AppState::Scores)
+                    .with_system(back_button_system.system())
+                    .with_system(browse_system.system()),
+            );
+    }
+}
+
+struct ScoresPage(Difficulty);
+
+fn title_text(difficulty: Difficulty) -> String {
+    format!("< scores - {} >\n", difficulty.name())
+}
+
+fn table_text(highscores: &HighScores, difficulty: Difficulty) -> String {
+    let table = highscores.table(difficulty);
+    if table.is_empty() {
+        return "no scores yet".to_string();
+    }
+    table
+        .iter()
+        .enumerate()
+        .map(|(rank, entry)| {
+            format!(
+                "{}. {} - {} pts - {} rows - {}",
+                rank + 1,
+                entry.name,
+                entry.points,
+                entry.rows,
+                format_time(entry.seconds)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn setup(
+    mut cmds: Commands,
+    mut snapshots: ResMut<Snapshots>,
+    mut page: ResMut<ScoresPage>,
+    button_materials: Res<ButtonMaterials>,
+    assets: Res<MyAssets>,
+    highscores: Res<HighScores>,
+    difficulty: Res<Difficulty>,
+) {
+    page.0 = *difficulty;
+    let entities = spawn_screen(
+        &mut cmds,
+        &button_materials,
+        &assets,
+        title_text(page.0),
+        table_text(&highscores, page.0),
+    );
+    snapshots.snap(&String::from("Scores"), entities);
+}
+
+fn clear(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
+    if let Some(entities) = snapshots.get_mut_snap(&String::from("Scores")) {
+        for entity in entities.iter() {
+            cmds.entity(*entity).despawn_recursive();
+        }
+        entities.clear();
+    }
+}
+
+fn browse_system(
+    mut actions: EventReader<Action>,
+    mut pad_events: EventReader<PadEvent>,
+    mut page: ResMut<ScoresPage>,
+    highscores: Res<HighScores>,
+    mut text_query: Query<&mut Text, With<ScreenText>>,
+) {
+    let mut step = 0;
+    for action in actions.iter() {
+        match action {
+            Action::MoveCursor(Direction::Left) => step -= 1,
+            Action::MoveCursor(Direction::Right) => step += 1,
+            _ => {}
+        }
+    }
+    for event in pad_events.iter() {
+        match event {
+            PadEvent::Left => step -= 1,
+            PadEvent::Right => step += 1,
+            _ => {}
+        }
+    }
+    if step == 0 {
+        return;
+    }
+    let count = Difficulty::ALL.len() as i32;
+    let index = Difficulty::ALL
+        .iter()
+        .position(|difficulty| *difficulty == page.0)
+        .unwrap_or(0) as i32;
+    page.0 = Difficulty::ALL[(index + step).rem_euclid(count) as usize];
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = title_text(page.0);
+        text.sections[1].value = table_text(&highscores, page.0);
+    }
+}
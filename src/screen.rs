@@ -0,0 +1,114 @@
+use crate::{
+    action::Action,
+    gamepad::PadEvent,
+    resource::{assets::Assets as MyAssets, button::ButtonMaterials},
+    state::AppState,
+};
+use bevy::prelude::*;
+
+pub struct BackButton;
+
+pub struct ScreenText;
+
+pub fn spawn_screen(
+    cmds: &mut Commands,
+    button_materials: &ButtonMaterials,
+    assets: &MyAssets,
+    title: String,
+    body: String,
+) -> Vec<Entity> {
+    let style = |font_size: f32, color: Color| TextStyle {
+        font: assets.font.clone(),
+        font_size,
+        color,
+    };
+    vec![
+        cmds.spawn_bundle(UiCameraBundle::default()).id(),
+        cmds.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                padding: Rect::all(Val::Px(16.0)),
+                ..Default::default()
+            },
+            material: button_materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection {
+                                value: title,
+                                style: style(24.0, Color::GOLD),
+                            },
+                            TextSection {
+                                value: body,
+                                style: style(14.0, Color::rgb(0.9, 0.9, 0.9)),
+                            },
+                        ],
+                        alignment: TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            vertical: VerticalAlign::Top,
+                        },
+                    },
+                    ..Default::default()
+                })
+                .insert(ScreenText);
+            parent
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(130.0), Val::Px(32.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    material: button_materials.normal.clone(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle {
+                        text: Text::with_section(
+                            "back",
+                            style(16.0, Color::rgb(0.9, 0.9, 0.9)),
+                            Default::default(),
+                        ),
+                        ..Default::default()
+                    });
+                })
+                .insert(BackButton);
+        })
+        .id(),
+    ]
+}
+
+#[allow(clippy::type_complexity)]
+pub fn back_button_system(
+    button_materials: Res<ButtonMaterials>,
+    mut actions: EventReader<Action>,
+    mut pad_events: EventReader<PadEvent>,
+    mut interaction_query: Query<
+        (&Interaction, &mut Handle<ColorMaterial>),
+        (Changed<Interaction>, With<BackButton>),
+    >,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    let menu = actions.iter().any(|action| *action == Action::Menu);
+    let cancel = pad_events
+        .iter()
+        .any(|event| *event == PadEvent::Cancel || *event == PadEvent::Start);
+    let mut back = menu || cancel;
+    for (interaction, mut material) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => back = true,
+            Interaction::Hovered => *material = button_materials.hovered.clone(),
+            Interaction::None => *material = button_materials.normal.clone(),
+        }
+    }
+    if back {
+        app_state.pop().unwrap();
+    }
+}
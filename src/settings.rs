@@ -0,0 +1,326 @@
+use crate::{
+    action::Action,
+    gamepad::PadEvent,
+    resource::{
+        assets::Assets as MyAssets, bindings::Bindings, button::ButtonMaterials,
+        display::DisplaySettings, snapshots::Snapshots,
+    },
+    state::AppState,
+};
+use bevy::prelude::*;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<Rebinding>()
+            .insert_resource(SettingsPage::Controls)
+            .add_system_set(SystemSet::on_enter(AppState::Settings).with_system(setup.system()))
+            .add_system_set(SystemSet::on_exit(AppState::Settings).with_system(clear.system()))
+            .add_system_set(
+                SystemSet::on_update(AppState::Settings)
+                    .with_system(button_system.system())
+                    .with_system(rebind_system.system())
+                    .with_system(label_system.system()),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsPage {
+    Controls,
+    Display,
+}
+
+impl SettingsPage {
+    fn title(&self) -> &'static str {
+        match self {
+            SettingsPage::Controls => "controls",
+            SettingsPage::Display => "display",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Rebinding {
+    action: Option<Action>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SettingsButton {
+    Bind(Action),
+    Mode,
+    Vsync,
+    Msaa,
+    Scale,
+    Back,
+}
+
+impl SettingsButton {
+    fn label(
+        &self,
+        bindings: &Bindings,
+        rebinding: &Rebinding,
+        display: &DisplaySettings,
+        window: &WindowDescriptor,
+        msaa: &Msaa,
+    ) -> String {
+        match self {
+            SettingsButton::Bind(action) if rebinding.action == Some(*action) => {
+                format!("{}: press a key, esc cancels", action.name())
+            }
+            SettingsButton::Bind(action) => {
+                let keys: Vec<String> = bindings
+                    .keys(*action)
+                    .iter()
+                    .map(|key| format!("{:?}", key))
+                    .collect();
+                if keys.is_empty() {
+                    format!("{}: -", action.name())
+                } else {
+                    format!("{}: {}", action.name(), keys.join(" / "))
+                }
+            }
+            SettingsButton::Mode => format!("mode {}", display.mode.name()),
+            SettingsButton::Vsync if display.vsync != window.vsync => {
+                format!("vsync {} - on restart", on_off(display.vsync))
+            }
+            SettingsButton::Vsync => format!("vsync {}", on_off(display.vsync)),
+            SettingsButton::Msaa if display.msaa != msaa.samples => {
+                format!("msaa {} - on restart", display.msaa)
+            }
+            SettingsButton::Msaa => format!("msaa {}", display.msaa),
+            SettingsButton::Scale => format!("ui scale {}", display.ui_scale),
+            SettingsButton::Back => "back".to_string(),
+        }
+    }
+
+    fn material(
+        &self,
+        button_materials: &ButtonMaterials,
+        rebinding: &Rebinding,
+    ) -> Handle<ColorMaterial> {
+        match self {
+            SettingsButton::Bind(action) if rebinding.action == Some(*action) => {
+                button_materials.selected.clone()
+            }
+            _ => button_materials.normal.clone(),
+        }
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn setup(
+    mut cmds: Commands,
+    mut snapshots: ResMut<Snapshots>,
+    mut rebinding: ResMut<Rebinding>,
+    button_materials: Res<ButtonMaterials>,
+    assets: Res<MyAssets>,
+    bindings: Res<Bindings>,
+    display: Res<DisplaySettings>,
+    window: Res<WindowDescriptor>,
+    msaa: Res<Msaa>,
+    page: Res<SettingsPage>,
+) {
+    rebinding.action = None;
+    let mut entities = vec![cmds.spawn_bundle(UiCameraBundle::default()).id()];
+    let mut buttons: Vec<SettingsButton> = match *page {
+        SettingsPage::Controls => bindings
+            .bindings
+            .iter()
+            .map(|binding| SettingsButton::Bind(binding.action))
+            .collect(),
+        SettingsPage::Display => vec![
+            SettingsButton::Mode,
+            SettingsButton::Vsync,
+            SettingsButton::Msaa,
+            SettingsButton::Scale,
+        ],
+    };
+    buttons.push(SettingsButton::Back);
+    entities.push(
+        cmds.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                style: Style {
+                    margin: Rect::all(Val::Px(8.0)),
+                    ..Default::default()
+                },
+                text: Text::with_section(
+                    page.title(),
+                    TextStyle {
+                        font: assets.font.clone(),
+                        font_size: 24.0,
+                        color: Color::GOLD,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+            for button in buttons {
+                parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(220.0), Val::Px(24.0)),
+                            margin: Rect::all(Val::Px(2.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        material: button.material(&button_materials, &rebinding),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                button.label(&bindings, &rebinding, &display, &window, &msaa),
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: 14.0,
+                                    color: Color::rgb(0.9, 0.9, 0.9),
+                                },
+                                Default::default(),
+                            ),
+                            ..Default::default()
+                        });
+                    })
+                    .insert(button);
+            }
+        })
+        .id(),
+    );
+    snapshots.snap(&String::from("Settings"), entities);
+}
+
+fn clear(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
+    if let Some(entities) = snapshots.get_mut_snap(&String::from("Settings")) {
+        for entity in entities.iter() {
+            cmds.entity(*entity).despawn_recursive();
+        }
+        entities.clear();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    button_materials: Res<ButtonMaterials>,
+    mut interaction_query: Query<
+        (&Interaction, &mut Handle<ColorMaterial>, &SettingsButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut app_state: ResMut<State<AppState>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut display: ResMut<DisplaySettings>,
+) {
+    for (interaction, mut material, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => match button {
+                SettingsButton::Bind(action) => {
+                    rebinding.action = Some(*action);
+                }
+                SettingsButton::Mode => {
+                    display.mode = display.mode.next();
+                }
+                SettingsButton::Vsync => {
+                    display.vsync = !display.vsync;
+                }
+                SettingsButton::Msaa => {
+                    display.msaa = display.next_msaa();
+                }
+                SettingsButton::Scale => {
+                    display.ui_scale = display.next_ui_scale();
+                }
+                SettingsButton::Back => {
+                    app_state.pop().unwrap();
+                }
+            },
+            Interaction::Hovered => {
+                *material = button_materials.hovered.clone();
+            }
+            Interaction::None => {
+                *material = button.material(&button_materials, &rebinding);
+            }
+        }
+    }
+}
+
+fn rebind_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut actions: EventReader<Action>,
+    mut pad_events: EventReader<PadEvent>,
+    mut app_state: ResMut<State<AppState>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<Bindings>,
+) {
+    let menu = actions.iter().any(|action| *action == Action::Menu);
+    match rebinding.action {
+        Some(action) => {
+            let cancel = pad_events.iter().any(|event| *event == PadEvent::Cancel);
+            match keyboard.get_just_pressed().next() {
+                Some(KeyCode::Escape) => rebinding.action = None,
+                Some(key) => {
+                    bindings.rebind(action, *key);
+                    bindings.save();
+                    rebinding.action = None;
+                }
+                None if cancel => rebinding.action = None,
+                None => {}
+            }
+        }
+        None => {
+            let back = pad_events
+                .iter()
+                .any(|event| *event == PadEvent::Cancel || *event == PadEvent::Start);
+            if back || menu {
+                app_state.pop().unwrap();
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn label_system(
+    button_materials: Res<ButtonMaterials>,
+    bindings: Res<Bindings>,
+    rebinding: Res<Rebinding>,
+    display: Res<DisplaySettings>,
+    window: Res<WindowDescriptor>,
+    msaa: Res<Msaa>,
+    query: Query<(
+        &Interaction,
+        &mut Handle<ColorMaterial>,
+        &SettingsButton,
+        &Children,
+    )>,
+    mut text_query: Query<&mut Text>,
+) {
+    if bindings.is_changed() || rebinding.is_changed() || display.is_changed() {
+        query.for_each_mut(|(interaction, mut material, button, children)| {
+            if *interaction == Interaction::None {
+                *material = button.material(&button_materials, &rebinding);
+            }
+            if let Ok(mut text) = text_query.get_mut(children[0]) {
+                text.sections[0].value =
+                    button.label(&bindings, &rebinding, &display, &window, &msaa);
+            }
+        });
+    }
+}
@@ -0,0 +1,190 @@
+use crate::engine::{evaluate, Code, Color, Try};
+use crate::resource::rules::GameRules;
+use std::collections::HashSet;
+
+// Upper bound on evaluations per guess, past which the minimax only
+// considers the remaining candidates instead of the whole code space.
+const BUDGET: usize = 2_000_000;
+
+pub fn all_codes(rules: &GameRules) -> Vec<Code> {
+    let palette = rules.palette();
+    let mut codes = Vec::new();
+    let mut indexes = vec![0usize; rules.code_length];
+    loop {
+        let code: Code = indexes.iter().map(|i| palette[*i]).collect();
+        if rules.is_valid(&code) {
+            codes.push(code);
+        }
+        let mut i = 0;
+        loop {
+            if i == indexes.len() {
+                return codes;
+            }
+            indexes[i] += 1;
+            if indexes[i] < palette.len() {
+                break;
+            }
+            indexes[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+pub fn is_consistent(code: &[Color], tries: &[Try]) -> bool {
+    contradiction(code, tries).is_none()
+}
+
+pub fn contradiction(code: &[Color], tries: &[Try]) -> Option<(usize, (usize, usize))> {
+    tries.iter().enumerate().find_map(|(i, tr)| {
+        let result = evaluate(&tr.code, code);
+        if result != (tr.good, tr.bad) {
+            Some((i, result))
+        } else {
+            None
+        }
+    })
+}
+
+pub fn candidates(rules: &GameRules, tries: &[Try]) -> Vec<Code> {
+    all_codes(rules)
+        .into_iter()
+        .filter(|code| is_consistent(code, tries))
+        .collect()
+}
+
+pub fn first_guess(rules: &GameRules) -> Code {
+    let palette = rules.palette();
+    (0..rules.code_length)
+        .map(|i| {
+            if rules.duplicates {
+                palette[(i / 2) % palette.len()]
+            } else {
+                palette[i % palette.len()]
+            }
+        })
+        .collect()
+}
+
+pub fn next_guess(rules: &GameRules, tries: &[Try]) -> Option<Code> {
+    if tries.is_empty() {
+        return Some(first_guess(rules));
+    }
+    let candidates = candidates(rules, tries);
+    if candidates.len() <= 2 {
+        return candidates.into_iter().next();
+    }
+    let all = all_codes(rules);
+    let pool: &[Code] = if all.len() * candidates.len() <= BUDGET {
+        &all
+    } else {
+        &candidates[..(BUDGET / candidates.len()).max(1).min(candidates.len())]
+    };
+    let consistent: HashSet<&Code> = candidates.iter().collect();
+    pool.iter()
+        .min_by_key(|guess| {
+            (
+                worst_case(guess, &candidates, rules.code_length),
+                !consistent.contains(guess),
+            )
+        })
+        .cloned()
+}
+
+fn worst_case(guess: &[Color], candidates: &[Code], code_length: usize) -> usize {
+    let mut partitions = vec![0usize; (code_length + 1) * (code_length + 1)];
+    for candidate in candidates {
+        let (good, bad) = evaluate(candidate, guess);
+        partitions[good * (code_length + 1) + bad] += 1;
+    }
+    partitions.into_iter().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::rules::Difficulty;
+    use std::collections::HashMap;
+
+    fn deepest_solve(rules: &GameRules, tries: &mut Vec<Try>, secrets: Vec<Code>) -> usize {
+        let guess = next_guess(rules, tries).expect("solver ran out of guesses");
+        let mut groups: HashMap<(usize, usize), Vec<Code>> = HashMap::new();
+        for secret in secrets {
+            groups
+                .entry(evaluate(&secret, &guess))
+                .or_insert_with(Vec::new)
+                .push(secret);
+        }
+        let mut deepest = 1;
+        for ((good, bad), group) in groups {
+            if good == rules.code_length {
+                continue;
+            }
+            tries.push(Try {
+                code: guess.clone(),
+                good,
+                bad,
+            });
+            deepest = deepest.max(1 + deepest_solve(rules, tries, group));
+            tries.pop();
+        }
+        deepest
+    }
+
+    #[test]
+    fn solves_every_classic_code_within_five_guesses() {
+        let rules = GameRules::classic();
+        let secrets = all_codes(&rules);
+        assert_eq!(secrets.len(), 1296);
+        assert!(deepest_solve(&rules, &mut Vec::new(), secrets) <= 5);
+    }
+
+    #[test]
+    fn all_codes_honors_unique_colors() {
+        assert_eq!(all_codes(&Difficulty::Easy.rules()).len(), 6 * 5 * 4);
+    }
+
+    #[test]
+    fn candidates_without_tries_is_every_code() {
+        let rules = GameRules::classic();
+        assert_eq!(candidates(&rules, &[]).len(), all_codes(&rules).len());
+    }
+
+    #[test]
+    fn candidates_match_every_feedback() {
+        let rules = GameRules::classic();
+        let secret: Code = vec![0, 1, 2, 3]
+            .into_iter()
+            .map(Color::from_value)
+            .collect();
+        let tries: Vec<Try> = [vec![0, 0, 1, 1], vec![2, 3, 4, 5]]
+            .iter()
+            .map(|values| {
+                let code: Code = values.iter().map(|v| Color::from_value(*v)).collect();
+                let (good, bad) = evaluate(&secret, &code);
+                Try { code, good, bad }
+            })
+            .collect();
+        let remaining = candidates(&rules, &tries);
+        assert!(remaining.contains(&secret));
+        for code in remaining.iter() {
+            for tr in tries.iter() {
+                assert_eq!(evaluate(code, &tr.code), (tr.good, tr.bad));
+            }
+        }
+    }
+
+    #[test]
+    fn candidates_after_exact_match_is_that_code() {
+        let rules = GameRules::classic();
+        let code: Code = vec![5, 4, 3, 2]
+            .into_iter()
+            .map(Color::from_value)
+            .collect();
+        let tries = vec![Try {
+            code: code.clone(),
+            good: 4,
+            bad: 0,
+        }];
+        assert_eq!(candidates(&rules, &tries), vec![code]);
+    }
+}
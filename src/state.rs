@@ -3,5 +3,9 @@ pub enum AppState {
     Loading,
     Menu,
     InGame,
+    Paused,
     Restart,
+    Settings,
+    Stats,
+    Scores,
 }
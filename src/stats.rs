@@ -0,0 +1,139 @@
+use crate::mastermind_shape_bundler as MSB;
+use crate::{
+    resource::{
+        assets::Assets as MyAssets, button::ButtonMaterials, color::MastermindColors,
+        snapshots::Snapshots, stats::Stats, structure::Structure,
+    },
+    screen::{back_button_system, spawn_screen},
+    state::AppState,
+};
+use bevy::prelude::*;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<Stats>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::Stats)
+                    .with_system(setup.system())
+                    .with_system(draw_histogram.system()),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Stats).with_system(clear.system()))
+            .add_system_set(
+                SystemSet::on_update(AppState::Stats).with_system(back_button_system.system()),
+            );
+    }
+}
+
+fn setup(
+    mut cmds: Commands,
+    mut snapshots: ResMut<Snapshots>,
+    button_materials: Res<ButtonMaterials>,
+    assets: Res<MyAssets>,
+    stats: Res<Stats>,
+) {
+    let entities = spawn_screen(
+        &mut cmds,
+        &button_materials,
+        &assets,
+        "stats\n".to_string(),
+        format!(
+            "played {} - wins {} - losses {}\nwin rate {}%\nstreak {} - best {}",
+            stats.played,
+            stats.wins,
+            stats.losses,
+            stats.win_rate(),
+            stats.current_streak,
+            stats.best_streak
+        ),
+    );
+    snapshots.snap(&String::from("Stats"), entities);
+}
+
+fn draw_histogram(
+    mut cmds: Commands,
+    mut snapshots: ResMut<Snapshots>,
+    window: Res<WindowDescriptor>,
+    structure: Res<Structure>,
+    colors: Res<MastermindColors>,
+    assets: Res<MyAssets>,
+    stats: Res<Stats>,
+) {
+    let mut bars: Vec<(String, usize)> = stats
+        .distribution
+        .iter()
+        .enumerate()
+        .map(|(rows, count)| ((rows + 1).to_string(), *count))
+        .collect();
+    bars.push(("x".to_string(), stats.losses));
+    let max_count = bars
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let left = 40.;
+    let max_width = window.width - left * 2.;
+    let bar_height = (window.height * 0.45 / bars.len() as f32 - 4.).min(24.);
+    let top = window.height * 0.72;
+    let mut entities = Vec::new();
+    for (i, (label, count)) in bars.iter().enumerate() {
+        let y = top - i as f32 * (bar_height + 4.) - bar_height / 2.;
+        let width = (*count as f32 / max_count as f32 * max_width).max(2.);
+        entities.push(
+            cmds.spawn_bundle(MSB::build_outlined_rect(
+                Transform {
+                    translation: structure.world_position(Vec2::new(left + width / 2., y)),
+                    ..Transform::default()
+                },
+                colors.bar_colors,
+                width,
+                bar_height,
+                1.0,
+            ))
+            .id(),
+        );
+        for (text, x) in [
+            (label.clone(), left / 2.),
+            (count.to_string(), left + width + 12.),
+        ]
+        .iter()
+        {
+            entities.push(
+                cmds.spawn_bundle(Text2dBundle {
+                    text: Text::with_section(
+                        text.clone(),
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: bar_height.max(10.) * 0.7,
+                            color: colors.remaining_color,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            vertical: VerticalAlign::Center,
+                        },
+                    ),
+                    transform: Transform {
+                        translation: structure.world_position(Vec2::new(*x, y)),
+                        ..Transform::default()
+                    },
+                    ..Default::default()
+                })
+                .id(),
+            );
+        }
+    }
+    snapshots.snap(&String::from("StatsHistogram"), entities);
+}
+
+fn clear(mut cmds: Commands, mut snapshots: ResMut<Snapshots>) {
+    for name in ["Stats", "StatsHistogram"].iter() {
+        if let Some(entities) = snapshots.get_mut_snap(&name.to_string()) {
+            for entity in entities.iter() {
+                cmds.entity(*entity).despawn_recursive();
+            }
+            entities.clear();
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "mastermind";
+
+pub fn config_path(file: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR).join(file))
+}
+
+pub fn load<T: DeserializeOwned>(file: &str) -> Result<Option<T>, String> {
+    let path = match config_path(file) {
+        Some(path) if path.exists() => path,
+        _ => return Ok(None),
+    };
+    let content = fs::read_to_string(&path)
+        .map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+    ron::de::from_str(&content)
+        .map(Some)
+        .map_err(|err| format!("cannot parse {}: {}", path.display(), err))
+}
+
+pub fn save<T: Serialize>(file: &str, value: &T) -> Result<(), String> {
+    let path = config_path(file).ok_or_else(|| "no config directory".to_string())?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("cannot create {}: {}", dir.display(), err))?;
+    }
+    let content = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|err| format!("cannot serialize {}: {}", file, err))?;
+    fs::write(&path, content).map_err(|err| format!("cannot write {}: {}", path.display(), err))
+}
+
+pub fn remove(file: &str) -> Result<(), String> {
+    match config_path(file) {
+        Some(path) if path.exists() => fs::remove_file(&path)
+            .map_err(|err| format!("cannot remove {}: {}", path.display(), err)),
+        _ => Ok(()),
+    }
+}
+
+pub fn exists(file: &str) -> bool {
+    config_path(file).map_or(false, |path| path.exists())
+}